@@ -4,8 +4,91 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const MINIMUM_LIQUIDITY: u64 = 100;
 
+#[constant]
+pub const MAX_LP_DECIMALS: u8 = 9;
+
+#[constant]
+pub const MIN_AMP: u64 = 1;
+
+#[constant]
+pub const MAX_AMP: u64 = 1_000_000;
+
+// 单次调整放大系数最多变为原来的 10 倍或 1/10
+#[constant]
+pub const MAX_AMP_CHANGE: u64 = 10;
+
+#[constant]
+pub const MIN_RAMP_DURATION: i64 = 86400;
+
+#[constant]
+pub const WEIGHT_ONE: u64 = 1_000_000;
+
+// 单侧权重不低于 1%
+#[constant]
+pub const MIN_WEIGHT: u64 = 10_000;
+
+// 恒定和曲线兑换比例的精度
+#[constant]
+pub const SUM_RATE_ONE: u64 = 1_000_000_000;
+
+// 单次调整恒定和兑换比例最多偏离当前值 1%（基点）
+#[constant]
+pub const MAX_SUM_RATE_CHANGE_BPS: u64 = 100;
+
 #[constant]
 pub const AUTHORITY_SEED: &str = "authority";
 
 #[constant]
 pub const LIQUIDITY_SEED: &str = "liquidity";
+
+#[constant]
+pub const LOCK_SEED: &str = "lock";
+
+#[constant]
+pub const FARM_SEED: &str = "farm";
+
+#[constant]
+pub const STAKE_SEED: &str = "stake";
+
+#[constant]
+pub const CL_POOL_SEED: &str = "cl_pool";
+
+#[constant]
+pub const TICK_ARRAY_SEED: &str = "tick_array";
+
+#[constant]
+pub const POSITION_SEED: &str = "position";
+
+#[constant]
+pub const TICK_ARRAY_SIZE: usize = 60;
+
+#[constant]
+pub const MULTI_POOL_SEED: &str = "multi_pool";
+
+#[constant]
+pub const MIN_MULTI_POOL_TOKENS: usize = 3;
+
+#[constant]
+pub const MAX_MULTI_POOL_TOKENS: usize = 8;
+
+#[constant]
+pub const OBSERVATION_SEED: &str = "observation";
+
+#[constant]
+pub const PRICE_FEED_SEED: &str = "price_feed";
+
+#[constant]
+pub const MAX_FARM_REWARDS: usize = 3;
+
+#[constant]
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+#[constant]
+pub const MAX_SYMBOL_LEN: usize = 10;
+
+// 代币没有可用的元数据简称时，取 mint 地址的前几位作为标签
+#[constant]
+pub const MINT_LABEL_LEN: usize = 6;
+
+#[constant]
+pub const MAX_URI_LEN: usize = 200;
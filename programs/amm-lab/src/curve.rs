@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use fixed::types::{I64F64, U64F64};
+
+use crate::{math::big_num::to_u64, state::{CurveType, Pool}};
+
+pub mod constant_product;
+pub mod stable;
+pub mod weighted;
+pub mod constant_sum;
+
+use constant_product::ConstantProductCurve;
+use stable::{stable_rates, StableCurve};
+use weighted::WeightedCurve;
+use constant_sum::ConstantSumCurve;
+
+
+// 两币池的定价曲线，储备量与存入数量均按 [a, b] 排列，a_to_b 表示以 a 兑换 b
+pub trait Curve {
+    fn reserves(&self) -> [u64; 2];
+
+    // 兑换 input 可获得的输出，返回 (扣费后的输入, 输出)
+    fn swap_out(&self, input: u64, a_to_b: bool) -> Result<(u64, u64)>;
+
+    // 恰好兑换出 output 所需的输入（含手续费）
+    fn swap_in(&self, output: u64, a_to_b: bool) -> Result<u64>;
+
+    // 当前储备量下的不变量，用于检查兑换前后池子没有亏损
+    fn invariant(&self) -> Result<u128>;
+
+    // 以 b 计价的 a 的边际价格
+    fn spot_price(&self) -> Result<U64F64>;
+
+    // 存入两侧代币，返回实际存入的数量与可获得的流动性（首笔存款未扣除最低流动性）
+    fn deposit_lp(&self, amounts: [u64; 2], total_supply: u64) -> Result<([u64; 2], u64)>;
+
+    // 只存入一侧代币，返回实际存入的数量与可获得的流动性
+    fn deposit_single(&self, amount: u64, a_side: bool, total_supply: u64) -> Result<(u64, u64)> {
+        let amounts = if a_side { [amount, 0] } else { [0, amount] };
+        let (_, liquidity) = self.deposit_lp(amounts, total_supply)?;
+        Ok((amount, liquidity))
+    }
+
+    // 赎回 lp 份流动性按比例取回的两侧代币
+    fn withdraw_amounts(&self, lp: u64, total_supply: u64) -> [u64; 2] {
+        self.reserves().map(|reserve| withdraw_amount(lp, reserve, total_supply))
+    }
+
+    // 铸造 lp 份流动性按比例需要存入的两侧代币
+    fn deposit_amounts(&self, lp: u64, total_supply: u64) -> [u64; 2] {
+        self.reserves().map(|reserve| deposit_amount(lp, reserve, total_supply))
+    }
+}
+
+
+// 按池子的曲线类型构造定价曲线，reserves 可以与池子当前的储备量不同
+pub fn pool_curve(pool: &Pool, reserves: [u64; 2], fee: u16, decimals: [u8; 2]) -> Result<Box<dyn Curve>> {
+    Ok(match pool.curve_type {
+        CurveType::ConstantProduct => Box::new(ConstantProductCurve { reserves, fee }),
+        CurveType::Stable => Box::new(StableCurve {
+            reserves,
+            fee,
+            amp: pool.current_amp(Clock::get()?.unix_timestamp),
+            rates: stable_rates(&decimals)
+        }),
+        CurveType::Weighted => Box::new(WeightedCurve {
+            reserves,
+            fee,
+            weights: [pool.weight_a, pool.weight_b]
+        }),
+        CurveType::ConstantSum => Box::new(ConstantSumCurve {
+            reserves,
+            fee,
+            rate: pool.current_sum_rate(Clock::get()?.unix_timestamp)
+        })
+    })
+}
+
+
+// 赎回 amount 份流动性可取回的某一侧代币数量，total_supply 包含锁定的最低流动性
+pub fn withdraw_amount(amount: u64, reserve: u64, total_supply: u64) -> u64 {
+    I64F64::from_num(amount)
+        .checked_mul(I64F64::from_num(reserve))
+        .unwrap()
+        .checked_div(I64F64::from_num(total_supply))
+        .unwrap().floor()
+        .to_num::<u64>()
+}
+
+
+// 铸造 amount 份流动性需要存入的某一侧代币数量，向上取整以保护池子
+pub fn deposit_amount(amount: u64, reserve: u64, total_supply: u64) -> u64 {
+    (amount as u128 * reserve as u128).div_ceil(total_supply as u128) as u64
+}
+
+
+// 扣费后需要 taxed_input 时用户应支付的输入，与兑换时向下取整的手续费一致
+pub fn input_before_fee(taxed_input: u128, fee: u16) -> Result<u64> {
+    to_u64((taxed_input * 10000).div_ceil(10000 - fee as u128))
+}
+
+
+// 按方向把 [a, b] 排列成 [输入, 输出]
+pub fn ordered<T: Copy>(pair: [T; 2], a_to_b: bool) -> [T; 2] {
+    if a_to_b { pair } else { [pair[1], pair[0]] }
+}
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use fixed::types::{I64F64, U64F64};
+use fixed_sqrt::FixedSqrt;
+
+use crate::{errors::ErrorCode, math::big_num::to_u64};
+
+use super::{input_before_fee, ordered, Curve};
+
+
+// 恒定乘积曲线 x * y = K
+pub struct ConstantProductCurve {
+    pub reserves: [u64; 2],
+    pub fee: u16
+}
+
+
+impl Curve for ConstantProductCurve {
+    fn reserves(&self) -> [u64; 2] {
+        self.reserves
+    }
+
+    fn swap_out(&self, input: u64, a_to_b: bool) -> Result<(u64, u64)> {
+        let [reserve_in, reserve_out] = ordered(self.reserves, a_to_b);
+        Ok(swap_output(input, self.fee, reserve_in, reserve_out))
+    }
+
+    fn swap_in(&self, output: u64, a_to_b: bool) -> Result<u64> {
+        let [reserve_in, reserve_out] = ordered(self.reserves, a_to_b);
+        require!(output < reserve_out, ErrorCode::InsufficientReserve);
+
+        // in = reserve_in * out / (reserve_out - out)，向上取整以保护池子
+        let taxed_input = (reserve_in as u128 * output as u128).div_ceil((reserve_out - output) as u128);
+        input_before_fee(taxed_input, self.fee)
+    }
+
+    fn invariant(&self) -> Result<u128> {
+        Ok(self.reserves[0] as u128 * self.reserves[1] as u128)
+    }
+
+    fn spot_price(&self) -> Result<U64F64> {
+        require!(self.reserves[0] > 0 && self.reserves[1] > 0, ErrorCode::EmptyPool);
+        U64F64::from_num(self.reserves[1])
+            .checked_div(U64F64::from_num(self.reserves[0]))
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    fn deposit_lp(&self, amounts: [u64; 2], total_supply: u64) -> Result<([u64; 2], u64)> {
+        let [reserve_a, reserve_b] = self.reserves;
+        let [amount_a, amount_b] = amounts;
+
+        // 按池子比例计算与 a 配对的 b，超出请求的数量时改由 b 决定 a，向下取整以保护池子
+        let (amount_a, amount_b) = if reserve_a == 0 && reserve_b == 0 {
+            (amount_a, amount_b)
+        } else {
+            let optimal_b = to_u64(amount_a as u128 * reserve_b as u128 / reserve_a as u128)?;
+            if optimal_b <= amount_b {
+                (amount_a, optimal_b)
+            } else {
+                (to_u64(amount_b as u128 * reserve_a as u128 / reserve_b as u128)?, amount_b)
+            }
+        };
+
+        // 计算即将返给投资者的资金量：首笔存款按 sqrt(a * b)，之后按存入占储备量的比例
+        let liquidity = if reserve_a == 0 && reserve_b == 0 {
+            liquidity_for_amounts(amount_a, amount_b)
+        } else {
+            proportional_liquidity([amount_a, amount_b], [reserve_a as u128, reserve_b as u128], total_supply)?
+        };
+
+        Ok(([amount_a, amount_b], liquidity))
+    }
+
+    fn deposit_single(&self, amount: u64, a_side: bool, total_supply: u64) -> Result<(u64, u64)> {
+        let [reserve_in, reserve_out] = ordered(self.reserves, a_side);
+
+        // 先通过池子自身的曲线兑换一部分输入，兑换得到的代币直接留在池子里作为另一侧的存款
+        let swap_amount = optimal_swap_amount(amount, self.fee, reserve_in, reserve_out);
+        let (_, output) = swap_output(swap_amount, self.fee, reserve_in, reserve_out);
+        require!(output > 0, ErrorCode::DepositTooSmall);
+
+        // 按兑换后的池子比例计算与输出配对的输入，向上取整以保护池子
+        let reserve_in_after = reserve_in as u128 + swap_amount as u128;
+        let reserve_out_after = (reserve_out - output) as u128;
+        let paired = (output as u128 * reserve_in_after).div_ceil(reserve_out_after);
+        let paired = (paired as u64).min(amount - swap_amount);
+
+        // 兑换不改变份额总量，按兑换后的储备量计算存入部分对应的份额
+        let liquidity = proportional_liquidity(
+            [paired, output], 
+            [reserve_in_after, reserve_out_after], 
+            total_supply
+        )?;
+
+        Ok((swap_amount + paired, liquidity))
+    }
+}
+
+
+// 扣除交易费后按 x * y = K 计算输出，返回 (扣费后的输入, 输出)
+pub fn swap_output(input: u64, fee: u16, reserve_in: u64, reserve_out: u64) -> (u64, u64) {
+    let taxed_input = input - input * fee as u64 / 10000;
+
+    let output = I64F64::from_num(taxed_input)
+        .checked_mul(I64F64::from_num(reserve_out))
+        .unwrap()
+        .checked_div(
+            I64F64::from_num(reserve_in)
+                .checked_add(I64F64::from_num(taxed_input))
+                .unwrap()
+        )
+        .unwrap()
+        .to_num::<u64>();
+
+    (taxed_input, output)
+}
+
+
+// 存入 amount_a 与 amount_b 可获得的流动性
+pub fn liquidity_for_amounts(amount_a: u64, amount_b: u64) -> u64 {
+    I64F64::from_num(amount_a)
+        .checked_mul(I64F64::from_num(amount_b))
+        .unwrap().sqrt()
+        .to_num::<u64>()
+}
+
+
+
+// 按比例存入时可获得的流动性：min(a * S / Ra, b * S / Rb)，向下取整以保护池子
+pub fn proportional_liquidity(amounts: [u64; 2], reserves: [u128; 2], total_supply: u64) -> Result<u64> {
+    require!(reserves[0] > 0 && reserves[1] > 0, ErrorCode::EmptyPool);
+
+    let liquidity = amounts
+        .iter()
+        .zip(reserves)
+        .map(|(&amount, reserve)| amount as u128 * total_supply as u128 / reserve)
+        .min()
+        .unwrap();
+
+    to_u64(liquidity)
+}
+
+
+// 单边存入时需要先兑换出去的数量：
+// 二分查找最大的 s，使兑换后剩余的输入与得到的输出不低于兑换后池子的比例
+pub fn optimal_swap_amount(amount: u64, fee: u16, reserve_in: u64, reserve_out: u64) -> u64 {
+    let (mut low, mut high) = (0u64, amount);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        let (_, output) = swap_output(mid, fee, reserve_in, reserve_out);
+
+        let remaining = (amount - mid) as u128 * (reserve_out - output) as u128;
+        let required = output as u128 * (reserve_in as u128 + mid as u128);
+        if remaining >= required {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    low
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_deposit_mints_geometric_mean() {
+        let curve = ConstantProductCurve { reserves: [0, 0], fee: 30 };
+        let (amounts, liquidity) = curve.deposit_lp([400, 900], 0).unwrap();
+        assert_eq!(amounts, [400, 900]);
+        assert_eq!(liquidity, 600);
+    }
+
+    #[test]
+    fn later_deposits_mint_against_total_supply() {
+        // 储备量 1000:4000，总份额 1000（不等于 sqrt(k) = 2000）
+        let curve = ConstantProductCurve { reserves: [1_000, 4_000], fee: 30 };
+        let (amounts, liquidity) = curve.deposit_lp([100, 1_000], 1_000).unwrap();
+        assert_eq!(amounts, [100, 400]);
+        assert_eq!(liquidity, 100);
+
+        let curve = ConstantProductCurve { reserves: [4_000, 1_000], fee: 30 };
+        let (amounts, liquidity) = curve.deposit_lp([1_000, 100], 1_000).unwrap();
+        assert_eq!(amounts, [400, 100]);
+        assert_eq!(liquidity, 100);
+
+        // 由储备量较大的一侧限制存款时，按这一侧计算另一侧，两侧都不能超出请求的数量
+        let curve = ConstantProductCurve { reserves: [1_000, 4_000], fee: 30 };
+        let (amounts, liquidity) = curve.deposit_lp([1_000, 100], 1_000).unwrap();
+        assert_eq!(amounts, [25, 100]);
+        assert_eq!(liquidity, 25);
+
+        let curve = ConstantProductCurve { reserves: [4_000, 1_000], fee: 30 };
+        let (amounts, liquidity) = curve.deposit_lp([100, 1_000], 1_000).unwrap();
+        assert_eq!(amounts, [100, 25]);
+        assert_eq!(liquidity, 25);
+    }
+
+    #[test]
+    fn swap_in_round_trips_through_swap_out() {
+        let curve = ConstantProductCurve { reserves: [1_000_000, 4_000_000], fee: 30 };
+        for (output, a_to_b) in [(1, true), (10_000, true), (1_000_000, true), (1, false), (10_000, false), (200_000, false)] {
+            let input = curve.swap_in(output, a_to_b).unwrap();
+            assert!(curve.swap_out(input, a_to_b).unwrap().1 >= output);
+            // 手续费向下取整，最多多付 1
+            assert!(curve.swap_out(input - 2, a_to_b).unwrap().1 < output);
+        }
+        assert!(curve.swap_in(4_000_000, true).is_err());
+    }
+
+    #[test]
+    fn single_sided_deposit_mints_less_than_balanced_deposit() {
+        let curve = ConstantProductCurve { reserves: [1_000_000, 1_000_000], fee: 30 };
+        let (input, liquidity) = curve.deposit_single(20_000, true, 500_000).unwrap();
+        assert!(input <= 20_000);
+        // 两侧各存入 10_000 可获得 5_000 份，单边存入要承担兑换手续费
+        assert!(liquidity < 5_000 && liquidity > 4_900);
+    }
+}
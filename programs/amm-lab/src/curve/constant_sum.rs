@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use fixed::types::U64F64;
+
+use crate::{
+    constants::SUM_RATE_ONE,
+    errors::ErrorCode,
+    math::big_num::{to_u128, to_u64, U256}
+};
+
+use super::{input_before_fee, ordered, Curve};
+
+
+// 恒定和曲线 x * rate + y = K，在任意一侧耗尽之前都按固定比例兑换
+pub struct ConstantSumCurve {
+    pub reserves: [u64; 2],
+    pub fee: u16,
+    pub rate: u64
+}
+
+
+impl ConstantSumCurve {
+    // 按 b 计价并放大 SUM_RATE_ONE 倍的总价值，避免换算时的舍入
+    fn value(&self, amounts: [u64; 2]) -> u128 {
+        amounts[0] as u128 * self.rate as u128 + amounts[1] as u128 * SUM_RATE_ONE as u128
+    }
+
+    // 以 [输入, 输出] 的比例换算数量，round_up 为 true 时向上取整
+    fn convert(&self, amount: u64, a_to_b: bool, round_up: bool) -> u128 {
+        let [rate_in, rate_out] = ordered([self.rate as u128, SUM_RATE_ONE as u128], a_to_b);
+        if round_up {
+            (amount as u128 * rate_in).div_ceil(rate_out)
+        } else {
+            amount as u128 * rate_in / rate_out
+        }
+    }
+}
+
+
+impl Curve for ConstantSumCurve {
+    fn reserves(&self) -> [u64; 2] {
+        self.reserves
+    }
+
+    fn swap_out(&self, input: u64, a_to_b: bool) -> Result<(u64, u64)> {
+        let [_, reserve_out] = ordered(self.reserves, a_to_b);
+        let taxed_input = input - input * self.fee as u64 / 10000;
+
+        let output = to_u64(self.convert(taxed_input, a_to_b, false))?;
+        require!(output <= reserve_out, ErrorCode::InsufficientReserve);
+
+        Ok((taxed_input, output))
+    }
+
+    fn swap_in(&self, output: u64, a_to_b: bool) -> Result<u64> {
+        let [_, reserve_out] = ordered(self.reserves, a_to_b);
+        require!(output <= reserve_out, ErrorCode::InsufficientReserve);
+
+        // 反向换算时交换输入输出的比例
+        input_before_fee(self.convert(output, !a_to_b, true), self.fee)
+    }
+
+    fn invariant(&self) -> Result<u128> {
+        Ok(self.value(self.reserves))
+    }
+
+    fn spot_price(&self) -> Result<U64F64> {
+        Ok(U64F64::from_num(self.rate) / U64F64::from_num(SUM_RATE_ONE))
+    }
+
+    // 流动性按两侧储备量的总价值计算，超出等比例的部分相当于兑换，收取手续费
+    fn deposit_lp(&self, amounts: [u64; 2], total_supply: u64) -> Result<([u64; 2], u64)> {
+        let deposit_value = self.value(amounts);
+        require!(deposit_value > 0, ErrorCode::DepositTooSmall);
+
+        // 首笔存款以按 b 计价的总价值作为流动性
+        if self.reserves == [0, 0] {
+            return Ok((amounts, to_u64(deposit_value / SUM_RATE_ONE as u128)?));
+        }
+
+        let pool_value = self.value(self.reserves);
+        let mut taxable = [0u64; 2];
+        for k in 0..2 {
+            let proportional = to_u128(
+                U256::from(self.reserves[k]) * U256::from(deposit_value) / U256::from(pool_value)
+            )?;
+            taxable[k] = (amounts[k] as u128).saturating_sub(proportional) as u64;
+        }
+        let fee_value = (self.value(taxable) * self.fee as u128).div_ceil(10000);
+
+        let liquidity = U256::from(total_supply) * U256::from(deposit_value - fee_value) / U256::from(pool_value);
+        Ok((amounts, to_u64(to_u128(liquidity)?)?))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_in_round_trips_through_swap_out() {
+        // 1 a 兑换 2 b
+        let curve = ConstantSumCurve { reserves: [1_000_000, 1_000_000], fee: 30, rate: 2 * SUM_RATE_ONE };
+        for (output, a_to_b) in [(1, true), (10_001, true), (900_000, true), (1, false), (10_001, false), (400_000, false)] {
+            let input = curve.swap_in(output, a_to_b).unwrap();
+            assert!(curve.swap_out(input, a_to_b).unwrap().1 >= output);
+            // 手续费向下取整，最多多付 1
+            assert!(curve.swap_out(input - 2, a_to_b).unwrap().1 < output);
+        }
+        assert!(curve.swap_in(1_000_001, true).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use fixed::types::U64F64;
+
+use crate::{
+    errors::ErrorCode, 
+    math::{big_num::{to_u128, to_u64, U256}, stable_math::{compute_d, compute_y, compute_y_d}}
+};
+
+use super::{input_before_fee, Curve};
+
+
+// StableSwap 曲线，amp 为当前的放大系数
+pub struct StableCurve {
+    pub reserves: [u64; 2],
+    pub fee: u16,
+    pub amp: u64,
+    pub rates: Vec<u128>
+}
+
+
+impl StableCurve {
+    fn indexes(a_to_b: bool) -> (usize, usize) {
+        if a_to_b { (0, 1) } else { (1, 0) }
+    }
+}
+
+
+impl Curve for StableCurve {
+    fn reserves(&self) -> [u64; 2] {
+        self.reserves
+    }
+
+    fn swap_out(&self, input: u64, a_to_b: bool) -> Result<(u64, u64)> {
+        let (i, j) = Self::indexes(a_to_b);
+        stable_swap_output(input, self.fee, self.amp, &self.reserves, &self.rates, i, j)
+    }
+
+    fn swap_in(&self, output: u64, a_to_b: bool) -> Result<u64> {
+        let (i, j) = Self::indexes(a_to_b);
+        require!(output < self.reserves[j], ErrorCode::InsufficientReserve);
+
+        // 多取出 1 抵消迭代误差，保证舍入对池子有利
+        let balances = stable_balances(&self.reserves, &self.rates);
+        let y = balances[j] - (output as u128 + 1) * self.rates[j];
+        let x = compute_y(self.amp, &balances, j, i, y)?;
+
+        let taxed_input = (x.saturating_sub(balances[i]) + 1).div_ceil(self.rates[i]);
+        input_before_fee(taxed_input, self.fee)
+    }
+
+    fn invariant(&self) -> Result<u128> {
+        stable_invariant(self.amp, &self.reserves, &self.rates)
+    }
+
+    // 由 F(x, y) = Ann * (x + y) + D - Ann * D - D^3 / (4xy) 求偏导：
+    // price = (t + y) / (t + x)，其中 t = 4 * Ann * x^2 * y^2 / D^3
+    fn spot_price(&self) -> Result<U64F64> {
+        require!(self.reserves[0] > 0 && self.reserves[1] > 0, ErrorCode::EmptyPool);
+
+        let balances = stable_balances(&self.reserves, &self.rates);
+        let d = U256::from(self.invariant()?);
+        let (x, y) = (U256::from(balances[0]), U256::from(balances[1]));
+
+        let ann = U256::from(self.amp) * U256::from(4u8);
+        let t = U256::from(4u8) * ann * x * y / d * x / d * y / d;
+
+        // 换算回各自精度下的原始数量
+        let numerator = ((t + y) * U256::from(self.rates[0])) << 64;
+        let denominator = (t + x) * U256::from(self.rates[1]);
+        Ok(U64F64::from_bits(to_u128(numerator / denominator)?))
+    }
+
+    fn deposit_lp(&self, amounts: [u64; 2], total_supply: u64) -> Result<([u64; 2], u64)> {
+        // 稳定曲线允许按任意比例存入，偏离池子比例的部分收取手续费
+        let liquidity = stable_deposit_liquidity(
+            &amounts, 
+            self.fee, 
+            self.amp, 
+            &self.reserves, 
+            &self.rates, 
+            total_supply
+        )?;
+
+        Ok((amounts, liquidity))
+    }
+}
+
+
+// 稳定曲线先把各代币余额放大到相同精度再计算
+pub fn stable_rates(decimals: &[u8]) -> Vec<u128> {
+    let max_decimals = decimals.iter().copied().max().unwrap_or_default();
+    decimals
+        .iter()
+        .map(|&d| 10u128.pow((max_decimals - d) as u32))
+        .collect()
+}
+
+
+fn stable_balances(reserves: &[u64], rates: &[u128]) -> Vec<u128> {
+    reserves.iter().zip(rates).map(|(&reserve, &rate)| reserve as u128 * rate).collect()
+}
+
+
+// 不平衡存取时每个代币按 fee * n / (4 * (n - 1)) 收取手续费，两个代币时为一半费率
+fn stable_imbalance_fee(fee: u16, n: usize) -> u128 {
+    fee as u128 * n as u128 / (4 * (n as u128 - 1))
+}
+
+
+// 统一精度后的稳定曲线不变量 D
+pub fn stable_invariant(amp: u64, reserves: &[u64], rates: &[u128]) -> Result<u128> {
+    compute_d(amp, &stable_balances(reserves, rates))
+}
+
+
+// 稳定曲线上以第 i 个代币兑换第 j 个代币的输出
+pub fn stable_swap_output(
+    input: u64, 
+    fee: u16, 
+    amp: u64, 
+    reserves: &[u64], 
+    rates: &[u128], 
+    i: usize, 
+    j: usize
+) -> Result<(u64, u64)> {
+    let taxed_input = input - input * fee as u64 / 10000;
+
+    let balances = stable_balances(reserves, rates);
+    let y = compute_y(amp, &balances, i, j, balances[i] + taxed_input as u128 * rates[i])?;
+
+    // 多减 1 抵消迭代误差，保证舍入对池子有利
+    let output = balances[j].saturating_sub(y).saturating_sub(1) / rates[j];
+
+    Ok((taxed_input, to_u64(output)?))
+}
+
+
+// 稳定曲线存入任意比例的代币可获得的流动性，偏离池子比例的部分收取手续费
+pub fn stable_deposit_liquidity(
+    amounts: &[u64], 
+    fee: u16, 
+    amp: u64, 
+    reserves: &[u64], 
+    rates: &[u128], 
+    total_supply: u64
+) -> Result<u64> {
+    let d0 = if reserves.iter().all(|&reserve| reserve == 0) {
+        0
+    } else {
+        stable_invariant(amp, reserves, rates)?
+    };
+
+    let mut balances = reserves
+        .iter()
+        .zip(amounts)
+        .zip(rates)
+        .map(|((&reserve, &amount), &rate)| (reserve as u128 + amount as u128) * rate)
+        .collect::<Vec<_>>();
+    let d1 = compute_d(amp, &balances)?;
+    require!(d1 > d0, ErrorCode::DepositTooSmall);
+
+    // 首笔存款直接以 D 作为流动性
+    if d0 == 0 {
+        return to_u64(d1);
+    }
+
+    let fee = stable_imbalance_fee(fee, reserves.len());
+    for (k, balance) in balances.iter_mut().enumerate() {
+        let ideal = U256::from(d1) * U256::from(reserves[k] as u128 * rates[k]) / U256::from(d0);
+        let difference = to_u128(ideal)?.abs_diff(*balance);
+        *balance -= difference * fee / 10000;
+    }
+    let d2 = compute_d(amp, &balances)?;
+
+    let liquidity = U256::from(total_supply) * U256::from(d2.saturating_sub(d0)) / U256::from(d0);
+    to_u64(to_u128(liquidity)?)
+}
+
+
+// 稳定曲线上赎回 amount 份流动性全部换成第 i 个代币可取回的数量
+pub fn stable_withdraw_single(
+    amount: u64, 
+    fee: u16, 
+    amp: u64, 
+    reserves: &[u64], 
+    rates: &[u128], 
+    i: usize, 
+    total_supply: u64
+) -> Result<u64> {
+    let balances = stable_balances(reserves, rates);
+    let d0 = compute_d(amp, &balances)?;
+    let d1 = d0 - to_u128(U256::from(amount) * U256::from(d0) / U256::from(total_supply))?;
+    let new_y = compute_y_d(amp, &balances, i, d1)?;
+
+    // 与等比例赎回相比多取出的部分收取手续费
+    let fee = stable_imbalance_fee(fee, reserves.len());
+    let mut reduced = balances.clone();
+    for (k, balance) in reduced.iter_mut().enumerate() {
+        let proportional = to_u128(U256::from(balances[k]) * U256::from(d1) / U256::from(d0))?;
+        let expected = if k == i {
+            proportional.saturating_sub(new_y)
+        } else {
+            balances[k] - proportional
+        };
+        *balance -= expected * fee / 10000;
+    }
+
+    let y = compute_y_d(amp, &reduced, i, d1)?;
+    let output = reduced[i].saturating_sub(y).saturating_sub(1) / rates[i];
+
+    to_u64(output)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_in_round_trips_through_swap_out() {
+        // 6 位与 9 位精度的稳定币对，按精度换算后储备量相当
+        let curve = StableCurve {
+            reserves: [1_000_000_000_000, 1_000_000_000_000_000],
+            fee: 4,
+            amp: 100,
+            rates: stable_rates(&[6, 9])
+        };
+        for (output, a_to_b) in [(1_000_000_000, true), (500_000_000_000_000, true), (1_000_000, false), (500_000_000_000, false)] {
+            let input = curve.swap_in(output, a_to_b).unwrap();
+            assert!(curve.swap_out(input, a_to_b).unwrap().1 >= output);
+            // 只多付迭代误差对应的极少量输入
+            assert!(curve.swap_out(input - input / 1_000_000 - 2, a_to_b).unwrap().1 < output);
+        }
+    }
+}
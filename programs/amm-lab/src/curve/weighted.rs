@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use fixed::types::{I64F64, U64F64};
+
+use crate::{constants::WEIGHT_ONE, errors::ErrorCode, math::weighted_math::{exp, ln, pow}};
+
+use super::{input_before_fee, ordered, Curve};
+
+
+// 加权乘积曲线 prod(x ^ w) = K
+pub struct WeightedCurve {
+    pub reserves: [u64; 2],
+    pub fee: u16,
+    pub weights: [u64; 2]
+}
+
+
+impl Curve for WeightedCurve {
+    fn reserves(&self) -> [u64; 2] {
+        self.reserves
+    }
+
+    fn swap_out(&self, input: u64, a_to_b: bool) -> Result<(u64, u64)> {
+        weighted_swap_output(input, self.fee, ordered(self.reserves, a_to_b), ordered(self.weights, a_to_b))
+    }
+
+    // in = reserve_in * ((reserve_out / (reserve_out - out)) ^ (w_out / w_in) - 1)
+    fn swap_in(&self, output: u64, a_to_b: bool) -> Result<u64> {
+        let [reserve_in, reserve_out] = ordered(self.reserves, a_to_b);
+        let [weight_in, weight_out] = ordered(self.weights, a_to_b);
+        require!(reserve_in > 0, ErrorCode::EmptyPool);
+        // 兑换时输出会多减 1，这里按多取出 1 计算
+        let output = output + 1;
+        require!(output < reserve_out, ErrorCode::InsufficientReserve);
+
+        let base = U64F64::from_num(reserve_out) / U64F64::from_num(reserve_out - output);
+        let exponent = I64F64::from_num(weight_out) / I64F64::from_num(weight_in);
+        let factor = pow(base, exponent)?.saturating_sub(U64F64::ONE);
+
+        // 多加 1 抵消定点数误差，保证舍入对池子有利
+        let taxed_input = reserve_in as u128 * factor.to_bits();
+        input_before_fee((taxed_input >> 64) + 1, self.fee)
+    }
+
+    fn invariant(&self) -> Result<u128> {
+        weighted_invariant(&self.reserves, &self.weights)
+    }
+
+    // price = (reserve_b / w_b) / (reserve_a / w_a)
+    fn spot_price(&self) -> Result<U64F64> {
+        require!(self.reserves[0] > 0 && self.reserves[1] > 0, ErrorCode::EmptyPool);
+        U64F64::from_num(self.reserves[1])
+            .checked_div(U64F64::from_num(self.reserves[0]))
+            .and_then(|ratio| {
+                ratio.checked_mul(U64F64::from_num(self.weights[0]) / U64F64::from_num(self.weights[1]))
+            })
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    fn deposit_lp(&self, amounts: [u64; 2], total_supply: u64) -> Result<([u64; 2], u64)> {
+        // 按加权不变量的增长比例铸造流动性
+        let liquidity = weighted_deposit_liquidity(
+            &amounts, 
+            self.fee, 
+            &self.reserves, 
+            &self.weights, 
+            total_supply
+        )?;
+
+        Ok((amounts, liquidity))
+    }
+}
+
+
+fn normalized_weight(weight: u64) -> I64F64 {
+    I64F64::from_num(weight) / I64F64::from_num(WEIGHT_ONE)
+}
+
+
+// 加权不变量 prod(x ^ w)，以 U64F64 的原始位表示，便于比较大小
+pub fn weighted_invariant(reserves: &[u64], weights: &[u64]) -> Result<u128> {
+    let mut log_sum = I64F64::ZERO;
+    for (&reserve, &weight) in reserves.iter().zip(weights) {
+        require!(reserve > 0, ErrorCode::EmptyPool);
+        log_sum += ln(U64F64::from_num(reserve))? * normalized_weight(weight);
+    }
+
+    Ok(exp(log_sum)?.to_bits())
+}
+
+
+// 加权曲线的兑换输出，reserves 与 weights 均按 [输入, 输出] 排列：
+// out = reserve_out * (1 - (reserve_in / (reserve_in + in)) ^ (w_in / w_out))
+pub fn weighted_swap_output(input: u64, fee: u16, reserves: [u64; 2], weights: [u64; 2]) -> Result<(u64, u64)> {
+    require!(reserves[0] > 0 && reserves[1] > 0, ErrorCode::EmptyPool);
+    let taxed_input = input - input * fee as u64 / 10000;
+
+    let base = U64F64::from_bits(
+        ((reserves[0] as u128) << 64) / (reserves[0] as u128 + taxed_input as u128)
+    );
+    let exponent = I64F64::from_num(weights[0]) / I64F64::from_num(weights[1]);
+    let factor = U64F64::ONE.saturating_sub(pow(base, exponent)?);
+
+    // 多减 1 抵消定点数误差，保证舍入对池子有利
+    let output = ((reserves[1] as u128 * factor.to_bits()) >> 64).saturating_sub(1);
+
+    Ok((taxed_input, output as u64))
+}
+
+
+// 加权曲线存入任意比例的代币，按不变量的增长比例铸造流动性，超出等比例的部分按兑换收取手续费
+pub fn weighted_deposit_liquidity(
+    amounts: &[u64], 
+    fee: u16, 
+    reserves: &[u64], 
+    weights: &[u64], 
+    total_supply: u64
+) -> Result<u64> {
+    // 首笔存款以加权几何平均数作为流动性
+    if reserves.iter().all(|&reserve| reserve == 0) {
+        return Ok(U64F64::from_bits(weighted_invariant(amounts, weights)?).to_num::<u64>());
+    }
+    require!(reserves.iter().all(|&reserve| reserve > 0), ErrorCode::EmptyPool);
+
+    // 等比例存入时每个代币余额的平均增幅
+    let mut proportional_growth = U64F64::ZERO;
+    for k in 0..reserves.len() {
+        let growth = U64F64::from_num(amounts[k])
+            .checked_div(U64F64::from_num(reserves[k]))
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        proportional_growth = proportional_growth
+            .checked_add(growth * U64F64::from_num(normalized_weight(weights[k])))
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    }
+
+    let mut log_ratio = I64F64::ZERO;
+    for k in 0..reserves.len() {
+        let proportional = (reserves[k] as u128 * proportional_growth.to_bits()) >> 64;
+        let taxable = (amounts[k] as u128).saturating_sub(proportional);
+        let fee_amount = (taxable * fee as u128).div_ceil(10000) as u64;
+
+        let balance = reserves[k]
+            .checked_add(amounts[k] - fee_amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        let ratio = U64F64::from_num(balance) / U64F64::from_num(reserves[k]);
+        log_ratio += ln(ratio)? * normalized_weight(weights[k]);
+    }
+
+    let growth = exp(log_ratio)?.saturating_sub(U64F64::ONE);
+    let liquidity = U64F64::from_num(total_supply)
+        .checked_mul(growth)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    Ok(liquidity.to_num::<u64>())
+}
+
+
+// 加权曲线上赎回 amount 份流动性全部换成一个代币可取回的数量：
+// out = reserve * (1 - (1 - amount / supply) ^ (1 / w))，其中相当于兑换的部分收取手续费
+pub fn weighted_withdraw_single(amount: u64, fee: u16, reserve: u64, weight: u64, total_supply: u64) -> Result<u64> {
+    require!(amount < total_supply, ErrorCode::InputTooLarge);
+
+    let remaining = U64F64::from_bits((((total_supply - amount) as u128) << 64) / total_supply as u128);
+    let exponent = I64F64::from_num(WEIGHT_ONE) / I64F64::from_num(weight);
+    let factor = U64F64::ONE.saturating_sub(pow(remaining, exponent)?);
+    let amount_without_fee = (reserve as u128 * factor.to_bits()) >> 64;
+
+    // 等比例赎回本应取得的 weight 部分不收手续费
+    let taxable = amount_without_fee * (WEIGHT_ONE - weight) as u128 / WEIGHT_ONE as u128;
+    let fee_amount = (taxable * fee as u128).div_ceil(10000);
+
+    Ok(amount_without_fee.saturating_sub(fee_amount).saturating_sub(1) as u64)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_in_round_trips_through_swap_out() {
+        // 80/20 池
+        let curve = WeightedCurve {
+            reserves: [4_000_000_000, 1_000_000_000],
+            fee: 30,
+            weights: [800_000, 200_000]
+        };
+        for (output, a_to_b) in [(1_000_000, true), (500_000_000, true), (1_000_000, false), (2_000_000_000, false)] {
+            let input = curve.swap_in(output, a_to_b).unwrap();
+            assert!(curve.swap_out(input, a_to_b).unwrap().1 >= output);
+            // 只多付定点数误差对应的极少量输入
+            assert!(curve.swap_out(input - input / 1_000_000 - 2, a_to_b).unwrap().1 < output);
+        }
+        assert!(curve.swap_in(1_000_000_000, true).is_err());
+    }
+}
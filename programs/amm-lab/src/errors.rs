@@ -13,5 +13,116 @@ pub enum ErrorCode {
     OutputTooSmall,
 
     #[msg("Invariant does not hold")]
-    InvariantViolated
+    InvariantViolated,
+
+    #[msg("Token is not part of the pool")]
+    InvalidMint,
+
+    #[msg("Pool has no liquidity")]
+    EmptyPool,
+
+    #[msg("Input is above the maximum expected")]
+    InputTooLarge,
+
+    #[msg("Invalid LP token metadata")]
+    InvalidMetadata,
+
+    #[msg("Invalid LP decimals or minimum liquidity")]
+    InvalidLpConfig,
+
+    #[msg("Unlock time must be in the future and not earlier than the current lock")]
+    InvalidUnlockTime,
+
+    #[msg("Cannot change the release schedule of an existing lock")]
+    LockScheduleMismatch,
+
+    #[msg("No locked liquidity can be released yet")]
+    NothingToUnlock,
+
+    #[msg("Farm already has the maximum number of rewards")]
+    TooManyRewards,
+
+    #[msg("Reward does not exist on this farm")]
+    InvalidReward,
+
+    #[msg("Staked amount is too small")]
+    InsufficientStake,
+
+    #[msg("Tick is out of range or not aligned with the tick spacing")]
+    InvalidTick,
+
+    #[msg("Tick array does not match the pool or the tick")]
+    InvalidTickArray,
+
+    #[msg("Square root price is out of range")]
+    InvalidSqrtPrice,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Position does not have enough liquidity")]
+    InsufficientLiquidity,
+
+    #[msg("Position does not belong to the pool")]
+    InvalidPosition,
+
+    #[msg("Invalid amplification coefficient")]
+    InvalidAmp,
+
+    #[msg("Invalid amplification ramp")]
+    InvalidRamp,
+
+    #[msg("Pool does not use the stable curve")]
+    NotStablePool,
+
+    #[msg("Invalid pool weights")]
+    InvalidWeight,
+
+    #[msg("Curve is not supported by this pool")]
+    UnsupportedCurve,
+
+    #[msg("Multi pool must hold three to eight tokens")]
+    InvalidTokenCount,
+
+    #[msg("Pool already has liquidity")]
+    PoolAlreadyActive,
+
+    #[msg("Output exceeds pool reserves")]
+    InsufficientReserve,
+
+    #[msg("Invalid constant sum rate")]
+    InvalidRate,
+
+    #[msg("Pool does not use the constant sum curve")]
+    NotConstantSumPool,
+
+    #[msg("Pool is in emergency mode")]
+    PoolInEmergency,
+
+    #[msg("Treasury liquidity account is missing")]
+    MissingTreasuryAccount,
+
+    #[msg("Observations must be in increasing time order")]
+    InvalidObservation,
+
+    #[msg("Requested time is older than the oldest observation")]
+    ObservationTooOld,
+
+    #[msg("Invalid observation buffer size")]
+    InvalidObservationCapacity,
+
+    #[msg("Price feed is missing or does not match the pool")]
+    InvalidPriceFeed,
+
+    #[msg("Reference price is stale")]
+    StalePrice,
+
+    #[msg("Execution price deviates too far from the reference price")]
+    PriceDeviationTooLarge,
+
+    #[msg("Token program does not own the mint")]
+    InvalidTokenProgram,
+
+    #[msg("Observations account is required once initialized")]
+    MissingObservations
 } 
@@ -1,12 +1,96 @@
 pub mod create_amm;
 pub mod create_pool;
+pub mod create_pool_with_liquidity;
+pub mod set_lp_metadata_uri;
+pub mod set_lp_config;
+pub mod set_protocol_fee;
+pub mod update_lp_metadata_uri;
 pub mod deposit_liquidity;
+pub mod deposit_single_sided;
+pub mod deposit_exact_liquidity;
 pub mod withdraw_liquidity;
+pub mod withdraw_single_sided;
 pub mod swap_exact_tokens_for_tokens;
+pub mod init_observations;
+pub mod grow_observations;
+pub mod observe;
+pub mod get_pool_info;
+pub mod lock_liquidity;
+pub mod unlock_liquidity;
+pub mod create_farm;
+pub mod add_farm_reward;
+pub mod set_farm_emission;
+pub mod stake_liquidity;
+pub mod unstake_liquidity;
+pub mod claim_rewards;
+pub mod sync;
+pub mod skim;
+pub mod create_cl_pool;
+pub mod init_tick_array;
+pub mod open_position;
+pub mod increase_liquidity;
+pub mod decrease_liquidity;
+pub mod collect_fees;
+pub mod cl_swap;
+pub mod ramp_amp;
+pub mod set_sum_rate;
+pub mod set_emergency;
+pub mod create_price_feed;
+pub mod update_price_feed;
+pub mod set_price_feed;
+pub mod create_multi_pool;
+pub mod add_multi_pool_token;
+pub mod deposit_multi_liquidity;
+pub mod deposit_multi_single_sided;
+pub mod withdraw_multi_liquidity;
+pub mod withdraw_multi_single_sided;
+pub mod multi_swap;
 mod shared;
 
 pub use create_amm::*;
 pub use create_pool::*;
+pub use create_pool_with_liquidity::*;
+pub use set_lp_metadata_uri::*;
+pub use set_lp_config::*;
+pub use set_protocol_fee::*;
+pub use update_lp_metadata_uri::*;
 pub use deposit_liquidity::*;
+pub use deposit_single_sided::*;
+pub use deposit_exact_liquidity::*;
 pub use withdraw_liquidity::*;
+pub use withdraw_single_sided::*;
 pub use swap_exact_tokens_for_tokens::*;
+pub use init_observations::*;
+pub use grow_observations::*;
+pub use observe::*;
+pub use get_pool_info::*;
+pub use lock_liquidity::*;
+pub use unlock_liquidity::*;
+pub use create_farm::*;
+pub use add_farm_reward::*;
+pub use set_farm_emission::*;
+pub use stake_liquidity::*;
+pub use unstake_liquidity::*;
+pub use claim_rewards::*;
+pub use sync::*;
+pub use skim::*;
+pub use create_cl_pool::*;
+pub use init_tick_array::*;
+pub use open_position::*;
+pub use increase_liquidity::*;
+pub use decrease_liquidity::*;
+pub use collect_fees::*;
+pub use cl_swap::*;
+pub use ramp_amp::*;
+pub use set_sum_rate::*;
+pub use set_emergency::*;
+pub use create_price_feed::*;
+pub use update_price_feed::*;
+pub use set_price_feed::*;
+pub use create_multi_pool::*;
+pub use add_multi_pool_token::*;
+pub use deposit_multi_liquidity::*;
+pub use deposit_multi_single_sided::*;
+pub use withdraw_multi_liquidity::*;
+pub use withdraw_multi_single_sided::*;
+pub use multi_swap::*;
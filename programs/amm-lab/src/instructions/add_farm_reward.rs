@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{FARM_SEED, MAX_FARM_REWARDS}, 
+    errors::ErrorCode, 
+    state::{Amm, Farm, Pool, RewardInfo}
+};
+
+
+impl<'info> AddFarmReward<'info> {
+    pub fn add_farm_reward(&mut self, emission_per_second: u64) -> Result<()> {
+        let farm = &mut self.farm;
+        require!(farm.rewards.len() < MAX_FARM_REWARDS, ErrorCode::TooManyRewards);
+        require!(
+            farm.rewards.iter().all(|reward| reward.mint != self.reward_mint.key()), 
+            ErrorCode::InvalidReward
+        );
+
+        farm.update_rewards(Clock::get()?.unix_timestamp);
+        farm.rewards.push(RewardInfo {
+            mint: self.reward_mint.key(),
+            vault: self.reward_vault.key(),
+            emission_per_second,
+            reward_per_share: 0,
+            undistributed: 0
+        });
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct AddFarmReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(has_one = amm)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.key().as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump,
+        has_one = pool
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // 奖励金库，任何人都可以直接向其转账为奖励注资
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = reward_token_program
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub reward_token_program: Interface<'info, TokenInterface>
+}
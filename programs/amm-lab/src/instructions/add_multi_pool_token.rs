@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MAX_MULTI_POOL_TOKENS, MIN_WEIGHT, MULTI_POOL_SEED, WEIGHT_ONE}, 
+    errors::ErrorCode, 
+    state::{CurveType, MultiPool, PoolToken}
+};
+
+
+impl<'info> AddMultiPoolToken<'info> {
+    pub fn add_multi_pool_token(&mut self, weight: u64) -> Result<()> {
+        // 成员只能在首笔存款之前加入
+        require!(self.mint_liquidity.supply == 0, ErrorCode::PoolAlreadyActive);
+
+        let pool = &mut self.multi_pool;
+        require!(pool.tokens.len() < MAX_MULTI_POOL_TOKENS, ErrorCode::InvalidTokenCount);
+        require!(
+            pool.tokens.iter().all(|token| token.mint != self.mint.key()), 
+            ErrorCode::InvalidMint
+        );
+
+        let weight = if pool.curve_type == CurveType::Weighted {
+            require!((MIN_WEIGHT..WEIGHT_ONE).contains(&weight), ErrorCode::InvalidWeight);
+            weight
+        } else {
+            0
+        };
+
+        pool.tokens.push(PoolToken {
+            mint: self.mint.key(),
+            vault: self.pool_account.key(),
+            decimals: self.mint.decimals,
+            weight,
+            reserve: 0
+        });
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct AddMultiPoolToken<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = creator
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = multi_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
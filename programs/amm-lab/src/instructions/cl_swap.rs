@@ -0,0 +1,296 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::CL_POOL_SEED, 
+    errors::ErrorCode, 
+    math::{
+        liquidity_math::add_delta, 
+        swap_math::compute_swap_step, 
+        tick_math::{
+            get_sqrt_price_at_tick, 
+            get_tick_at_sqrt_price, 
+            MAX_SQRT_PRICE_X64, 
+            MAX_TICK, 
+            MIN_SQRT_PRICE_X64, 
+            MIN_TICK
+        }
+    }, 
+    state::{Amm, ClPool, TickArray}
+};
+
+use super::shared::{amount_after_transfer_fee, amount_before_transfer_fee, transfer_token};
+
+
+impl<'info> ClSwap<'info> {
+    pub fn cl_swap(
+        &mut self, 
+        a_to_b: bool,
+        input_amount: u64,
+        min_output_amount: u64,
+        sqrt_price_limit_x64: u128,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: &ClSwapBumps
+    ) -> Result<()> {
+        require!(input_amount > 0, ErrorCode::DepositTooSmall);
+
+        // 按池子扣除转账手续费后实际收到的数量兑换
+        let (in_mint, out_mint) = if a_to_b {
+            (self.a_mint.to_account_info(), self.b_mint.to_account_info())
+        } else {
+            (self.b_mint.to_account_info(), self.a_mint.to_account_info())
+        };
+        let received_input = amount_after_transfer_fee(&in_mint, input_amount)?;
+
+        let fee = self.amm.fee;
+        let pool = &mut self.cl_pool;
+        let pool_key = pool.key();
+        let tick_spacing = pool.tick_spacing;
+        let spacing = tick_spacing as i32;
+        let width = TickArray::width(tick_spacing);
+
+        require!(
+            if a_to_b {
+                sqrt_price_limit_x64 < pool.sqrt_price_x64 && sqrt_price_limit_x64 > MIN_SQRT_PRICE_X64
+            } else {
+                sqrt_price_limit_x64 > pool.sqrt_price_x64 && sqrt_price_limit_x64 < MAX_SQRT_PRICE_X64
+            },
+            ErrorCode::InvalidSqrtPrice
+        );
+
+        // 剩余账户按兑换方向依次传入相邻的 tick 数组，第一个必须包含当前 tick
+        let tick_arrays = remaining_accounts
+            .iter()
+            .map(AccountLoader::<TickArray>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        require!(!tick_arrays.is_empty(), ErrorCode::InvalidTickArray);
+
+        let first_start = TickArray::start_index(pool.tick_current, tick_spacing);
+        for (i, loader) in tick_arrays.iter().enumerate() {
+            let tick_array = loader.load()?;
+            let step = if a_to_b { -width } else { width };
+            require_keys_eq!(tick_array.pool, pool_key, ErrorCode::InvalidTickArray);
+            require!(
+                tick_array.start_tick_index == first_start + step * i as i32,
+                ErrorCode::InvalidTickArray
+            );
+        }
+
+        let mut amount_remaining = received_input;
+        let mut amount_out: u64 = 0;
+        let mut sqrt_price_x64 = pool.sqrt_price_x64;
+        let mut tick_current = pool.tick_current;
+        let mut liquidity = pool.liquidity;
+        let mut fee_growth_global_x64 = if a_to_b {
+            pool.fee_growth_global_a_x64
+        } else {
+            pool.fee_growth_global_b_x64
+        };
+        let mut array_index = 0;
+
+        while amount_remaining > 0 && sqrt_price_x64 != sqrt_price_limit_x64 {
+            let mut tick_array = tick_arrays
+                .get(array_index)
+                .ok_or(error!(ErrorCode::InvalidTickArray))?
+                .load_mut()?;
+            let start = tick_array.start_tick_index;
+
+            // 当前数组沿兑换方向已经没有可用的 tick，切换到下一个数组
+            if (a_to_b && tick_current < start) || (!a_to_b && tick_current >= start + width - spacing) {
+                array_index += 1;
+                continue;
+            }
+
+            // 数组内没有已初始化的 tick 时先移动到数组边界
+            let (tick_next, initialized) = match tick_array.next_initialized_tick(tick_current, tick_spacing, a_to_b) {
+                Some(tick) => (tick, true),
+                None if a_to_b => (start, false),
+                None => (start + width - spacing, false)
+            };
+            let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+            let sqrt_price_next_tick_x64 = get_sqrt_price_at_tick(tick_next)?;
+
+            let sqrt_price_target_x64 = if a_to_b {
+                sqrt_price_next_tick_x64.max(sqrt_price_limit_x64)
+            } else {
+                sqrt_price_next_tick_x64.min(sqrt_price_limit_x64)
+            };
+
+            let step = compute_swap_step(
+                sqrt_price_x64, 
+                sqrt_price_target_x64, 
+                liquidity, 
+                amount_remaining, 
+                fee
+            )?;
+
+            amount_remaining = amount_remaining
+                .checked_sub(step.amount_in)
+                .and_then(|amount| amount.checked_sub(step.fee_amount))
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            amount_out = amount_out.checked_add(step.amount_out).ok_or(error!(ErrorCode::MathOverflow))?;
+
+            // 手续费按活跃流动性累计
+            if let Some(fee_growth) = ((step.fee_amount as u128) << 64).checked_div(liquidity) {
+                fee_growth_global_x64 = fee_growth_global_x64.wrapping_add(fee_growth);
+            }
+
+            sqrt_price_x64 = step.sqrt_price_next_x64;
+            if sqrt_price_x64 == sqrt_price_next_tick_x64 {
+                if initialized {
+                    let (fee_growth_global_a_x64, fee_growth_global_b_x64) = if a_to_b {
+                        (fee_growth_global_x64, pool.fee_growth_global_b_x64)
+                    } else {
+                        (pool.fee_growth_global_a_x64, fee_growth_global_x64)
+                    };
+
+                    let offset = tick_array.tick_offset(tick_next, tick_spacing)?;
+                    let liquidity_net = tick_array.ticks[offset].cross(fee_growth_global_a_x64, fee_growth_global_b_x64);
+                    let liquidity_net = if a_to_b {
+                        liquidity_net.checked_neg().ok_or(error!(ErrorCode::MathOverflow))?
+                    } else {
+                        liquidity_net
+                    };
+                    liquidity = add_delta(liquidity, liquidity_net)?;
+                }
+
+                tick_current = if a_to_b { tick_next - 1 } else { tick_next };
+            } else {
+                tick_current = get_tick_at_sqrt_price(sqrt_price_x64)?;
+            }
+        }
+
+        // 到达价格限制时只转入实际用掉的部分（含转账手续费），最低输出按用户实际到账的数量检查
+        let input = if amount_remaining == 0 {
+            input_amount
+        } else {
+            amount_before_transfer_fee(&in_mint, received_input - amount_remaining)?
+        };
+        let received_output = amount_after_transfer_fee(&out_mint, amount_out)?;
+        require!(received_output >= min_output_amount, ErrorCode::OutputTooSmall);
+
+        let pool = &mut self.cl_pool;
+        pool.sqrt_price_x64 = sqrt_price_x64;
+        pool.tick_current = tick_current;
+        pool.liquidity = liquidity;
+        if a_to_b {
+            pool.fee_growth_global_a_x64 = fee_growth_global_x64;
+        } else {
+            pool.fee_growth_global_b_x64 = fee_growth_global_x64;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.cl_pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            CL_POOL_SEED.as_bytes(),
+            &[bumps.cl_pool]
+        ]];
+
+        let (trader_in, pool_in, mint_in, trader_out, pool_out, mint_out) = if a_to_b {
+            (&self.trader_account_a, &self.pool_account_a, &self.a_mint, &self.trader_account_b, &self.pool_account_b, &self.b_mint)
+        } else {
+            (&self.trader_account_b, &self.pool_account_b, &self.b_mint, &self.trader_account_a, &self.pool_account_a, &self.a_mint)
+        };
+
+        transfer_token(
+            trader_in.to_account_info(), 
+            pool_in.to_account_info(), 
+            mint_in.to_account_info(), 
+            self.trader.to_account_info(), 
+            self.token_program.to_account_info(), 
+            input, 
+            mint_in.decimals, 
+            None, 
+            &[]
+        )?;
+
+        transfer_token(
+            pool_out.to_account_info(), 
+            trader_out.to_account_info(), 
+            mint_out.to_account_info(), 
+            self.cl_pool.to_account_info(), 
+            self.token_program.to_account_info(), 
+            amount_out, 
+            mint_out.decimals, 
+            Some(signer_seeds), 
+            &[]
+        )?;
+
+        msg!("Traded {} tokens for {}", input, received_output);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct ClSwap<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub trader: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            CL_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = a_mint,
+        associated_token::authority = trader,
+        associated_token::token_program = token_program
+    )]
+    pub trader_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = b_mint,
+        associated_token::authority = trader,
+        associated_token::token_program = token_program
+    )]
+    pub trader_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{constants::{FARM_SEED, STAKE_SEED}, errors::ErrorCode, state::{Farm, UserStake}};
+
+use super::shared::transfer_token;
+
+
+impl<'info> ClaimRewards<'info> {
+    pub fn claim_rewards(&mut self, index: u8, bumps: &ClaimRewardsBumps) -> Result<()> {
+        let farm = &mut self.farm;
+        farm.update_rewards(Clock::get()?.unix_timestamp);
+
+        let stake = &mut self.stake;
+        stake.settle(farm);
+
+        // 金库余额不足时只发放现有部分，其余继续留在待领取中
+        let index = index as usize;
+        let amount = stake.pending_rewards[index].min(self.reward_vault.amount);
+        require!(amount > 0, ErrorCode::OutputTooSmall);
+        stake.pending_rewards[index] -= amount;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.farm.pool.to_bytes(),
+            FARM_SEED.as_bytes(),
+            &[bumps.farm]
+        ]];
+
+        transfer_token(
+            self.reward_vault.to_account_info(), 
+            self.owner_account_reward.to_account_info(), 
+            self.reward_mint.to_account_info(), 
+            self.farm.to_account_info(), 
+            self.reward_token_program.to_account_info(), 
+            amount, 
+            self.reward_mint.decimals, 
+            Some(signer_seeds), 
+            &[]
+        )?;
+
+        msg!("Claimed {} rewards", amount);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            farm.pool.as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump,
+        constraint = farm.rewards.get(index as usize).map(|reward| reward.vault) 
+            == Some(reward_vault.key()) @ ErrorCode::InvalidReward
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            farm.key().as_ref(),
+            owner.key().as_ref(),
+            STAKE_SEED.as_bytes()
+        ],
+        bump,
+        has_one = farm,
+        has_one = owner
+    )]
+    pub stake: Box<Account<'info, UserStake>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = reward_token_program
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = reward_token_program
+    )]
+    pub owner_account_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub reward_token_program: Interface<'info, TokenInterface>
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{CL_POOL_SEED, POSITION_SEED}, 
+    errors::ErrorCode, 
+    state::{ClPool, Position, TickArray}
+};
+
+use super::shared::{modify_cl_position, transfer_token, with_position_ticks};
+
+
+impl<'info> CollectFees<'info> {
+    pub fn collect_fees(&mut self, bumps: &CollectFeesBumps) -> Result<()> {
+        // 流动性变化量为 0，只结算手续费
+        let pool = &mut self.cl_pool;
+        let position = &mut self.position;
+        with_position_ticks(
+            &self.tick_array_lower, 
+            &self.tick_array_upper, 
+            pool.key(), 
+            pool.tick_spacing, 
+            position.tick_lower, 
+            position.tick_upper, 
+            |lower, upper| modify_cl_position(pool, position, lower, upper, 0)
+        )?;
+
+        let amount_a = position.tokens_owed_a;
+        let amount_b = position.tokens_owed_b;
+        position.tokens_owed_a = 0;
+        position.tokens_owed_b = 0;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.cl_pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            CL_POOL_SEED.as_bytes(),
+            &[bumps.cl_pool]
+        ]];
+
+        if amount_a > 0 {
+            transfer_token(
+                self.pool_account_a.to_account_info(), 
+                self.owner_account_a.to_account_info(), 
+                self.a_mint.to_account_info(), 
+                self.cl_pool.to_account_info(), 
+                self.token_program.to_account_info(), 
+                amount_a, 
+                self.a_mint.decimals, 
+                Some(signer_seeds), 
+                &[]
+            )?;
+        }
+
+        if amount_b > 0 {
+            transfer_token(
+                self.pool_account_b.to_account_info(), 
+                self.owner_account_b.to_account_info(), 
+                self.b_mint.to_account_info(), 
+                self.cl_pool.to_account_info(), 
+                self.token_program.to_account_info(), 
+                amount_b, 
+                self.b_mint.decimals, 
+                Some(signer_seeds), 
+                &[]
+            )?;
+        }
+
+        msg!("Collected {} token a and {} token b in fees", amount_a, amount_b);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    pub owner: Signer<'info>,
+
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            cl_pool.amm.as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            CL_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position.position_mint.as_ref()
+        ],
+        bump,
+        constraint = position.pool == cl_pool.key() @ ErrorCode::InvalidPosition
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    // 持有仓位 NFT 才能操作仓位
+    #[account(
+        token::mint = position.position_mint,
+        token::authority = owner,
+        token::token_program = position_token_program,
+        constraint = owner_position_account.amount == 1 @ ErrorCode::InvalidPosition
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>
+}
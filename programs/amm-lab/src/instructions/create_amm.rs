@@ -1,14 +1,16 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, state::Amm};
+use crate::{constants::{MAX_LP_DECIMALS, MINIMUM_LIQUIDITY}, errors::ErrorCode, state::Amm};
 
 
 impl<'info> CreateAmm<'info> {
-    pub fn create_amm(&mut self, id: Pubkey, fee: u16) -> Result<()> {
+    pub fn create_amm(&mut self, id: Pubkey, fee: u16, lp_decimals: u8, minimum_liquidity: u64) -> Result<()> {
         let amm_account = &mut self.amm_acount;
         amm_account.id = id;
         amm_account.fee = fee;
         amm_account.admin = self.admin.key();
+        amm_account.lp_decimals = lp_decimals;
+        amm_account.minimum_liquidity = minimum_liquidity;
         
         Ok(())
     }
@@ -16,7 +18,7 @@ impl<'info> CreateAmm<'info> {
 
 
 #[derive(Accounts)]
-#[instruction(id: Pubkey, fee: u16)]
+#[instruction(id: Pubkey, fee: u16, lp_decimals: u8, minimum_liquidity: u64)]
 pub struct CreateAmm<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -26,10 +28,12 @@ pub struct CreateAmm<'info> {
     #[account(
         init,
         payer = signer,
-        space = Amm::INIT_SPACE,
+        space = 8 + Amm::INIT_SPACE,
         seeds = [id.as_ref()],
         bump,
         constraint = fee < 10000 @ ErrorCode::InvalidFee,
+        constraint = lp_decimals <= MAX_LP_DECIMALS @ ErrorCode::InvalidLpConfig,
+        constraint = minimum_liquidity >= MINIMUM_LIQUIDITY @ ErrorCode::InvalidLpConfig,
     )]
     pub amm_acount: Box<Account<'info, Amm>>,
 
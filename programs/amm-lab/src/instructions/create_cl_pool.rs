@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::CL_POOL_SEED, 
+    errors::ErrorCode, 
+    math::tick_math::get_tick_at_sqrt_price, 
+    state::{Amm, ClPool}
+};
+
+
+impl<'info> CreateClPool<'info> {
+    pub fn create_cl_pool(&mut self, tick_spacing: u16, sqrt_price_x64: u128) -> Result<()> {
+        require!(tick_spacing > 0, ErrorCode::InvalidTick);
+
+        let pool = &mut self.cl_pool;
+        pool.amm = self.amm.key();
+        pool.a_mint = self.a_mint.key();
+        pool.b_mint = self.b_mint.key();
+        pool.tick_spacing = tick_spacing;
+        pool.sqrt_price_x64 = sqrt_price_x64;
+        pool.tick_current = get_tick_at_sqrt_price(sqrt_price_x64)?;
+        pool.liquidity = 0;
+        pool.fee_growth_global_a_x64 = 0;
+        pool.fee_growth_global_b_x64 = 0;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct CreateClPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    // 集中流动性池子本身作为金库的所有者
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + ClPool::INIT_SPACE,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            CL_POOL_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = a_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = b_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{constants::{FARM_SEED, LIQUIDITY_SEED}, state::{Amm, Farm, Pool}};
+
+
+impl<'info> CreateFarm<'info> {
+    pub fn create_farm(&mut self) -> Result<()> {
+        let farm = &mut self.farm;
+        farm.pool = self.pool.key();
+        farm.mint_liquidity = self.mint_liquidity.key();
+        farm.total_staked = 0;
+        farm.last_update_ts = Clock::get()?.unix_timestamp;
+        farm.rewards = vec![];
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct CreateFarm<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(has_one = amm)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            pool.a_mint.as_ref(),
+            pool.b_mint.as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Farm::INIT_SPACE,
+        seeds = [
+            pool.key().as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = farm,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub farm_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::Token2022, token_interface::Mint};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MAX_AMP, MIN_AMP, MULTI_POOL_SEED}, 
+    errors::ErrorCode, 
+    state::{Amm, CurveType, MultiPool}
+};
+
+
+impl<'info> CreateMultiPool<'info> {
+    pub fn create_multi_pool(&mut self, id: Pubkey, curve_type: CurveType, amp: u64) -> Result<()> {
+        match curve_type {
+            CurveType::Stable => require!((MIN_AMP..=MAX_AMP).contains(&amp), ErrorCode::InvalidAmp),
+            CurveType::Weighted => {},
+            CurveType::ConstantProduct | CurveType::ConstantSum => return err!(ErrorCode::UnsupportedCurve)
+        }
+
+        let pool = &mut self.multi_pool;
+        pool.amm = self.amm.key();
+        pool.id = id;
+        pool.creator = self.signer.key();
+        pool.curve_type = curve_type;
+        pool.amp = amp;
+        pool.lp_decimals = self.amm.lp_decimals;
+        pool.minimum_liquidity = self.amm.minimum_liquidity;
+        pool.tokens = vec![];
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(id: Pubkey)]
+pub struct CreateMultiPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    // 池子本身作为各成员金库与流动性代币的权限
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + MultiPool::INIT_SPACE,
+        seeds = [
+            amm.key().as_ref(),
+            id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+        mint::decimals = amm.lp_decimals,
+        mint::authority = multi_pool,
+        mint::token_program = liquidity_token_program
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
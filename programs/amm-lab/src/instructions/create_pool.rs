@@ -1,22 +1,142 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenInterface};
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    token_2022::Token2022,
+    token_interface::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::TokenMetadata,
+        token_metadata_initialize, Mint, TokenMetadataInitialize
+    }
+};
+
+use crate::{
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MAX_AMP, MIN_AMP, MIN_WEIGHT, WEIGHT_ONE}, 
+    errors::ErrorCode, 
+    state::{Amm, Pool, PoolCurve}
+};
 
-use crate::{state::{Amm, Pool}, constants::{AUTHORITY_SEED, LIQUIDITY_SEED}};
+use super::shared::mint_symbol;
 
 
 impl<'info> CreatePool<'info> {
-    pub fn create_pool(&mut self) -> Result<()> {
+    pub fn create_pool(&mut self, curve: PoolCurve, bumps: &CreatePoolBumps) -> Result<()> {
         let pool = &mut self.pool;
         pool.amm = self.amm.key();
         pool.a_mint = self.a_mint.key();
         pool.b_mint = self.b_mint.key();
-        
+        pool.lp_decimals = self.amm.lp_decimals;
+        pool.minimum_liquidity = self.amm.minimum_liquidity;
+        pool.curve_type = curve.curve_type();
+
+        match curve {
+            PoolCurve::ConstantProduct => {},
+            PoolCurve::Stable { amp } => self.set_stable_curve(amp)?,
+            PoolCurve::Weighted { weight_a } => self.set_weighted_curve(weight_a)?,
+            PoolCurve::ConstantSum { sum_rate } => self.set_constant_sum_curve(sum_rate)?
+        }
+
+        self.initialize_lp_metadata(bumps)
+    }
+
+    // 稳定币对使用 StableSwap 曲线，放大系数之后可以由管理员逐步调整
+    fn set_stable_curve(&mut self, amp: u64) -> Result<()> {
+        require!((MIN_AMP..=MAX_AMP).contains(&amp), ErrorCode::InvalidAmp);
+
+        let pool = &mut self.pool;
+        pool.initial_amp = amp;
+        pool.target_amp = amp;
+
+        Ok(())
+    }
+
+    // 加权池按权重定价，例如 80/20 的治理代币池，weight_b 由 WEIGHT_ONE - weight_a 推出
+    fn set_weighted_curve(&mut self, weight_a: u64) -> Result<()> {
+        require!(
+            (MIN_WEIGHT..=WEIGHT_ONE - MIN_WEIGHT).contains(&weight_a),
+            ErrorCode::InvalidWeight
+        );
+
+        let pool = &mut self.pool;
+        pool.weight_a = weight_a;
+        pool.weight_b = WEIGHT_ONE - weight_a;
+
         Ok(())
     }
+
+    // 同一资产的不同包装版本按固定比例兑换，兑换比例之后可以由管理员调整
+    fn set_constant_sum_curve(&mut self, sum_rate: u64) -> Result<()> {
+        require!(sum_rate > 0, ErrorCode::InvalidRate);
+
+        let pool = &mut self.pool;
+        pool.initial_sum_rate = sum_rate;
+        pool.sum_rate = sum_rate;
+
+        Ok(())
+    }
+
+    // 为流动性代币写入 Token-2022 元数据，名称由两种代币自身的元数据推导，例如 "SOL-USDC LP"
+    fn initialize_lp_metadata(&mut self, bumps: &CreatePoolBumps) -> Result<()> {
+        let symbol_a = mint_symbol(&self.a_mint.to_account_info())?;
+        let symbol_b = mint_symbol(&self.b_mint.to_account_info())?;
+        let name = format!("{}-{} LP", symbol_a, symbol_b);
+        let symbol = format!("{}-{}-LP", symbol_a, symbol_b);
+        let uri = self.amm.lp_metadata_uri.clone();
+
+        // 元数据写在 mint 账户内，需要先补足租金
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(self.pool_authority.key()))?,
+            mint: self.mint_liquidity.key(),
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![]
+        };
+        let mint_info = self.mint_liquidity.to_account_info();
+        let space = mint_info.data_len() + metadata.tlv_size_of()?;
+        let lamports = Rent::get()?.minimum_balance(space).saturating_sub(mint_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(), 
+                    Transfer { 
+                        from: self.signer.to_account_info(), 
+                        to: mint_info.clone() 
+                    }
+                ), 
+                lamports
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.amm.key().to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.liquidity_token_program.to_account_info(), 
+                TokenMetadataInitialize { 
+                    program_id: self.liquidity_token_program.to_account_info(), 
+                    metadata: mint_info.clone(), 
+                    update_authority: self.pool_authority.to_account_info(), 
+                    mint_authority: self.pool_authority.to_account_info(), 
+                    mint: mint_info 
+                }, 
+                signer_seeds
+            ), 
+            name, 
+            symbol, 
+            uri
+        )
+    }
 }
 
 
 #[derive(Accounts)]
+#[instruction(curve: PoolCurve)]
 pub struct CreatePool<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -34,11 +154,12 @@ pub struct CreatePool<'info> {
     #[account(
         init,
         payer = signer,
-        space = Pool::INIT_SPACE,
+        space = 8 + Pool::INIT_SPACE,
         seeds = [
             amm.key().as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            curve.curve_type().seed(),
         ],
         bump
     )]
@@ -50,6 +171,7 @@ pub struct CreatePool<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            curve.curve_type().seed(),
             AUTHORITY_SEED.as_bytes()
         ],
         bump
@@ -63,14 +185,18 @@ pub struct CreatePool<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            curve.curve_type().seed(),
             LIQUIDITY_SEED.as_bytes()
         ],
         bump,
-        mint::decimals = 6,
-        mint::authority = pool_authority
+        mint::decimals = amm.lp_decimals,
+        mint::authority = pool_authority,
+        mint::token_program = liquidity_token_program,
+        extensions::metadata_pointer::authority = pool_authority,
+        extensions::metadata_pointer::metadata_address = mint_liquidity
     )]
     pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Interface<'info, TokenInterface>
+    pub liquidity_token_program: Program<'info, Token2022>
 }
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{TokenAccount, TokenInterface}
+};
+
+use crate::{constants::AUTHORITY_SEED, curve::pool_curve, errors::ErrorCode, state::PoolCurve};
+
+use super::{
+    create_pool::*, 
+    shared::{accumulate_price, amount_after_transfer_fee, mint_liquidity_tokens, transfer_token}
+};
+
+
+impl<'info> CreatePoolWithLiquidity<'info> {
+    // 在同一笔指令内创建池子并完成首笔存款，避免空池子被抢先设定价格
+    pub fn create_pool_with_liquidity(
+        &mut self, 
+        curve: PoolCurve, 
+        amount_a: u64, 
+        amount_b: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &CreatePoolWithLiquidityBumps
+    ) -> Result<()> {
+        self.create.create_pool(curve, &bumps.create)?;
+        accumulate_price(
+            &mut self.create.pool, 
+            self.create.amm.fee, 
+            [self.create.a_mint.decimals, self.create.b_mint.decimals]
+        )?;
+        require!(amount_a > 0 && amount_b > 0, ErrorCode::DepositTooSmall);
+
+        // 首笔存款按池子扣除转账手续费后实际收到的数量决定初始价格，并锁定最低流动性
+        let received_a = amount_after_transfer_fee(&self.create.a_mint.to_account_info(), amount_a)?;
+        let received_b = amount_after_transfer_fee(&self.create.b_mint.to_account_info(), amount_b)?;
+        let curve = pool_curve(
+            &self.create.pool, 
+            [0, 0], 
+            self.create.amm.fee, 
+            [self.create.a_mint.decimals, self.create.b_mint.decimals]
+        )?;
+        let (_, liquidity) = curve.deposit_lp([received_a, received_b], 0)?;
+
+        let minimum_liquidity = self.create.pool.minimum_liquidity;
+        require!(liquidity > minimum_liquidity, ErrorCode::DepositTooSmall);
+
+        transfer_token(
+            self.deposit_account_a.to_account_info(), 
+            self.pool_account_a.to_account_info(), 
+            self.create.a_mint.to_account_info(), 
+            self.create.signer.to_account_info(), 
+            self.token_program.to_account_info(), 
+            amount_a, 
+            self.create.a_mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+
+        transfer_token(
+            self.deposit_account_b.to_account_info(), 
+            self.pool_account_b.to_account_info(), 
+            self.create.b_mint.to_account_info(), 
+            self.create.signer.to_account_info(), 
+            self.token_program.to_account_info(), 
+            amount_b, 
+            self.create.b_mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+
+        let pool = &mut self.create.pool;
+        pool.reserve_a = received_a;
+        pool.reserve_b = received_b;
+        pool.update_k_last(self.create.amm.protocol_fee_on);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.create.amm.key().to_bytes(), 
+            &self.create.a_mint.key().to_bytes(), 
+            &self.create.b_mint.key().to_bytes(), 
+            self.create.pool.curve_type.seed(), 
+            AUTHORITY_SEED.as_bytes(), 
+            &[bumps.create.pool_authority]
+        ]];
+
+        mint_liquidity_tokens(
+            self.create.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.create.pool_authority.to_account_info(), 
+            self.create.liquidity_token_program.to_account_info(), 
+            liquidity - minimum_liquidity, 
+            signer_seeds
+        )?;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct CreatePoolWithLiquidity<'info> {
+    // 复用 create_pool 的账户与检查，signer 同时作为首笔存款人
+    pub create: CreatePool<'info>,
+
+    #[account(
+        init,
+        payer = create.signer,
+        associated_token::mint = create.a_mint,
+        associated_token::authority = create.pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = create.signer,
+        associated_token::mint = create.b_mint,
+        associated_token::authority = create.pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = create.a_mint,
+        associated_token::authority = create.signer,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = create.b_mint,
+        associated_token::authority = create.signer,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = create.signer,
+        associated_token::mint = create.mint_liquidity,
+        associated_token::authority = create.signer,
+        associated_token::token_program = create.liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
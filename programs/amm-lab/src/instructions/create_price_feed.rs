@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::PRICE_FEED_SEED, state::PriceFeed};
+
+
+impl<'info> CreatePriceFeed<'info> {
+    pub fn create_price_feed(&mut self, id: Pubkey, price: u128) -> Result<()> {
+        let price_feed = &mut self.price_feed;
+        price_feed.id = id;
+        price_feed.authority = self.authority.key();
+        price_feed.price = price;
+        price_feed.last_update_ts = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(id: Pubkey)]
+pub struct CreatePriceFeed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    ///CHECK: Read only, delegatable creation
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [
+            id.as_ref(),
+            PRICE_FEED_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub price_feed: Box<Account<'info, PriceFeed>>,
+
+    pub system_program: Program<'info, System>
+}
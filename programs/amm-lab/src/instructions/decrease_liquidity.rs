@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{CL_POOL_SEED, POSITION_SEED}, 
+    errors::ErrorCode, 
+    state::{ClPool, Position, TickArray}
+};
+
+use super::shared::{amount_after_transfer_fee, modify_cl_position, transfer_token, with_position_ticks};
+
+
+impl<'info> DecreaseLiquidity<'info> {
+    pub fn decrease_liquidity(
+        &mut self, 
+        liquidity: u128, 
+        min_amount_a: u64, 
+        min_amount_b: u64, 
+        bumps: &DecreaseLiquidityBumps
+    ) -> Result<()> {
+        require!(liquidity > 0, ErrorCode::OutputTooSmall);
+        require!(self.position.liquidity >= liquidity, ErrorCode::InsufficientLiquidity);
+        let liquidity_delta = -i128::try_from(liquidity).map_err(|_| error!(ErrorCode::MathOverflow))?;
+
+        let pool = &mut self.cl_pool;
+        let position = &mut self.position;
+        let (amount_a, amount_b) = with_position_ticks(
+            &self.tick_array_lower, 
+            &self.tick_array_upper, 
+            pool.key(), 
+            pool.tick_spacing, 
+            position.tick_lower, 
+            position.tick_upper, 
+            |lower, upper| modify_cl_position(pool, position, lower, upper, liquidity_delta)
+        )?;
+
+        // 最低数量按用户扣除转账手续费后实际到账的数量检查
+        require!(
+            amount_after_transfer_fee(&self.a_mint.to_account_info(), amount_a)? >= min_amount_a 
+                && amount_after_transfer_fee(&self.b_mint.to_account_info(), amount_b)? >= min_amount_b, 
+            ErrorCode::OutputTooSmall
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.cl_pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            CL_POOL_SEED.as_bytes(),
+            &[bumps.cl_pool]
+        ]];
+
+        if amount_a > 0 {
+            transfer_token(
+                self.pool_account_a.to_account_info(), 
+                self.owner_account_a.to_account_info(), 
+                self.a_mint.to_account_info(), 
+                self.cl_pool.to_account_info(), 
+                self.token_program.to_account_info(), 
+                amount_a, 
+                self.a_mint.decimals, 
+                Some(signer_seeds), 
+                &[]
+            )?;
+        }
+
+        if amount_b > 0 {
+            transfer_token(
+                self.pool_account_b.to_account_info(), 
+                self.owner_account_b.to_account_info(), 
+                self.b_mint.to_account_info(), 
+                self.cl_pool.to_account_info(), 
+                self.token_program.to_account_info(), 
+                amount_b, 
+                self.b_mint.decimals, 
+                Some(signer_seeds), 
+                &[]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct DecreaseLiquidity<'info> {
+    pub owner: Signer<'info>,
+
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            cl_pool.amm.as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            CL_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position.position_mint.as_ref()
+        ],
+        bump,
+        constraint = position.pool == cl_pool.key() @ ErrorCode::InvalidPosition
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    // 持有仓位 NFT 才能操作仓位
+    #[account(
+        token::mint = position.position_mint,
+        token::authority = owner,
+        token::token_program = position_token_program,
+        constraint = owner_position_account.amount == 1 @ ErrorCode::InvalidPosition
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = cl_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>
+}
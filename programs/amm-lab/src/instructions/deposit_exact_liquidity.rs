@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, curve::pool_curve, state::{Amm, Pool}, errors::ErrorCode};
+
+use super::shared::{
+    accumulate_price, 
+    amount_before_transfer_fee, 
+    mint_liquidity_tokens, 
+    mint_protocol_fee, 
+    transfer_token
+};
+
+
+impl<'info> DepositExactLiquidity<'info> {
+    pub fn deposit_exact_liquidity(
+        &mut self, 
+        lp_amount: u64, 
+        max_a: u64, 
+        max_b: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &DepositExactLiquidityBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
+        require!(self.pool.reserve_a > 0 && self.pool.reserve_b > 0, ErrorCode::EmptyPool);
+        require!(lp_amount > 0, ErrorCode::DepositTooSmall);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        // 先按 sqrt(k) 的增长向国库铸造协议费，再按铸造后的总份额计算本次存款
+        let fee_liquidity = mint_protocol_fee(
+            &self.pool, 
+            &self.amm, 
+            &self.mint_liquidity, 
+            self.treasury_account_liquidity.as_ref().map(|account| account.to_account_info()), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            signer_seeds
+        )?;
+        let total_supply = self.mint_liquidity.supply + self.pool.minimum_liquidity + fee_liquidity;
+
+        // 按当前储备量计算铸造 lp_amount 所需的两侧代币
+        let curve = pool_curve(
+            &self.pool, 
+            [self.pool.reserve_a, self.pool.reserve_b], 
+            self.amm.fee, 
+            [self.a_mint.decimals, self.b_mint.decimals]
+        )?;
+        let [amount_a, amount_b] = curve.deposit_amounts(lp_amount, total_supply);
+
+        // 用户需要多转出转账手续费，池子才能恰好收到所需的数量
+        let input_a = amount_before_transfer_fee(&self.a_mint.to_account_info(), amount_a)?;
+        let input_b = amount_before_transfer_fee(&self.b_mint.to_account_info(), amount_b)?;
+        require!(input_a <= max_a && input_b <= max_b, ErrorCode::InputTooLarge);
+
+        // 将资金存入流动池
+        transfer_token(
+            self.deposit_account_a.to_account_info(), 
+            self.pool_account_a.to_account_info(), 
+            self.a_mint.to_account_info(), 
+            self.deposit.to_account_info(), 
+            self.token_program.to_account_info(), 
+            input_a, 
+            self.a_mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+        
+        transfer_token(
+            self.deposit_account_b.to_account_info(), 
+            self.pool_account_b.to_account_info(), 
+            self.b_mint.to_account_info(), 
+            self.deposit.to_account_info(), 
+            self.token_program.to_account_info(), 
+            input_b, 
+            self.b_mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+
+        let pool = &mut self.pool;
+        pool.reserve_a = pool.reserve_a.checked_add(amount_a).unwrap();
+        pool.reserve_b = pool.reserve_b.checked_add(amount_b).unwrap();
+        pool.update_k_last(self.amm.protocol_fee_on);
+
+        // 铸造恰好 lp_amount 份流动性给用户
+        mint_liquidity_tokens(
+            self.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            lp_amount, 
+            signer_seeds
+        )?;
+        
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct DepositExactLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.a_mint.key().as_ref(), 
+            pool.b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = a_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = b_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = amm.treasury,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub treasury_account_liquidity: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = a_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = b_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
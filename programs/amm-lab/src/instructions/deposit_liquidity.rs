@@ -1,116 +1,128 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken, 
-    token_interface::{Mint, TokenAccount, TokenInterface, mint_to, MintTo}
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
 };
-use fixed::types::I64F64;
-use fixed_sqrt::FixedSqrt;
 
-use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MINIMUM_LIQUIDITY}, state::Pool, errors::ErrorCode};
+use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, curve::pool_curve, state::{Amm, Pool}, errors::ErrorCode};
 
-use super::shared::transfer_token;
+use super::shared::{
+    accumulate_price, 
+    amount_after_transfer_fee, 
+    amount_before_transfer_fee, 
+    mint_liquidity_tokens, 
+    mint_protocol_fee, 
+    transfer_token
+};
 
 
 impl<'info> DepositLiquidity<'info> {
-    pub fn deposit_liquidity(&mut self, amount_a: u64, amount_b: u64, bumps: &DepositLiquidityBumps) -> Result<()> {
+    pub fn deposit_liquidity(
+        &mut self, 
+        amount_a: u64, 
+        amount_b: u64, 
+        min_liquidity_out: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &DepositLiquidityBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
         // 判断金额是否正确
-        let mut amount_a = if amount_a > self.deposit_account_a.amount {
+        let amount_a = if amount_a > self.deposit_account_a.amount {
             self.deposit_account_a.amount
         } else {
             amount_a
         };
 
-        let mut amount_b = if amount_b > self.deposit_account_b.amount {
+        let amount_b = if amount_b > self.deposit_account_b.amount {
             self.deposit_account_b.amount
         } else {
             amount_b
         };
 
-        // 根据 x * y = K 推导实际需要存入的流动性
-        let pool_a = &self.pool_account_a;
-        let pool_b = &self.pool_account_b;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
 
-        let pool_creation = pool_a.amount == 0 && pool_b.amount == 0;
-        (amount_a, amount_b) = if pool_creation {
-            (amount_a, amount_b)
-        } else {
-            let ratio = I64F64::from_num(pool_a.amount)
-                .checked_div(I64F64::from_num(pool_b.amount))
-                .unwrap();
-            if pool_a.amount > pool_b.amount {
-                (
-                    I64F64::from_num(amount_b)
-                        .checked_mul(ratio)
-                        .unwrap()
-                        .to_num::<u64>(),
-                    amount_b,
-                )
-            } else {
-                (
-                    amount_a,
-                    I64F64::from_num(amount_a)
-                        .checked_mul(ratio)
-                        .unwrap()
-                        .to_num::<u64>()
-                )
-            }
-        };
-         
-        // 计算即将返给投资者的资金量
-        let mut liqidity = I64F64::from_num(amount_a)
-            .checked_mul(I64F64::from_num(amount_b))
-            .unwrap().sqrt()
-            .to_num::<u64>();
+        // 先按 sqrt(k) 的增长向国库铸造协议费，再按铸造后的总份额计算本次存款
+        let fee_liquidity = mint_protocol_fee(
+            &self.pool, 
+            &self.amm, 
+            &self.mint_liquidity, 
+            self.treasury_account_liquidity.as_ref().map(|account| account.to_account_info()), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            signer_seeds
+        )?;
+        let total_supply = self.mint_liquidity.supply + self.pool.minimum_liquidity + fee_liquidity;
+
+        let reserves = [self.pool.reserve_a, self.pool.reserve_b];
+        let pool_creation = reserves == [0, 0];
+
+        // 按池子扣除转账手续费后实际收到的数量计算存入的数量与可获得的流动性
+        let curve = pool_curve(&self.pool, reserves, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+        let ([amount_a, amount_b], mut liqidity) = curve.deposit_lp(
+            [
+                amount_after_transfer_fee(&self.a_mint.to_account_info(), amount_a)?, 
+                amount_after_transfer_fee(&self.b_mint.to_account_info(), amount_b)?
+            ], 
+            total_supply
+        )?;
         
         // 锁定第一笔存款的最低流动性
         if pool_creation {
-            require!(liqidity >= MINIMUM_LIQUIDITY, ErrorCode::DepositTooSmall);
-            liqidity -= MINIMUM_LIQUIDITY;
+            let minimum_liquidity = self.pool.minimum_liquidity;
+            require!(liqidity >= minimum_liquidity, ErrorCode::DepositTooSmall);
+            liqidity -= minimum_liquidity;
         };
 
-        // 将资金存入流动池
+        require!(liqidity >= min_liquidity_out, ErrorCode::OutputTooSmall);
+
+        // 将资金存入流动池，转出的数量包含转账手续费
         transfer_token(
             self.deposit_account_a.to_account_info(), 
             self.pool_account_a.to_account_info(), 
             self.a_mint.to_account_info(), 
             self.deposit.to_account_info(), 
             self.token_program.to_account_info(), 
-            amount_a, 
+            amount_before_transfer_fee(&self.a_mint.to_account_info(), amount_a)?, 
             self.a_mint.decimals, 
-            None
+            None, 
+            remaining_accounts
         )?;
         
         transfer_token(
-            self.deposit_account_a.to_account_info(), 
+            self.deposit_account_b.to_account_info(), 
             self.pool_account_b.to_account_info(), 
             self.b_mint.to_account_info(), 
             self.deposit.to_account_info(), 
             self.token_program.to_account_info(), 
-            amount_b, 
+            amount_before_transfer_fee(&self.b_mint.to_account_info(), amount_b)?, 
             self.b_mint.decimals, 
-            None
+            None, 
+            remaining_accounts
         )?;
 
-        // 将此次出入获取到的代币转给用户
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            &self.pool.amm.to_bytes(),
-            &self.a_mint.key().to_bytes(),
-            &self.b_mint.key().to_bytes(),
-            AUTHORITY_SEED.as_bytes(),
-            &[bumps.pool_authority]
-        ]];
+        let pool = &mut self.pool;
+        pool.reserve_a = pool.reserve_a.checked_add(amount_a).unwrap();
+        pool.reserve_b = pool.reserve_b.checked_add(amount_b).unwrap();
+        pool.update_k_last(self.amm.protocol_fee_on);
 
-        mint_to(
-        CpiContext::new_with_signer(
-        self.token_program.to_account_info(), 
-        MintTo { 
-                    mint: self.mint_liquidity.to_account_info(), 
-                    to: self.deposit_account_liquidity.to_account_info(), 
-                    authority: self.pool_authority.to_account_info() 
-                }, 
-                signer_seeds
-            ),
-            liqidity
+        // 将此次出入获取到的代币转给用户
+        mint_liquidity_tokens(
+            self.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            liqidity, 
+            signer_seeds
         )?;
         
         Ok(())
@@ -127,14 +139,24 @@ pub struct DepositLiquidity<'info> {
     pub b_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
         seeds = [
             pool.amm.as_ref(),
             pool.a_mint.key().as_ref(), 
             pool.b_mint.key().as_ref(),
+            pool.curve_type.seed(),
         ],
         bump,
+        has_one = amm,
         has_one = a_mint,
-        has_one = b_mint
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -144,6 +166,7 @@ pub struct DepositLiquidity<'info> {
             pool.amm.as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             AUTHORITY_SEED.as_bytes()
         ],
         bump
@@ -156,6 +179,7 @@ pub struct DepositLiquidity<'info> {
             pool.amm.as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             LIQUIDITY_SEED.as_bytes()
         ],
         bump,
@@ -185,10 +209,18 @@ pub struct DepositLiquidity<'info> {
         payer = payer,
         associated_token::mint = mint_liquidity,
         associated_token::authority = deposit,
-        associated_token::token_program = token_program
+        associated_token::token_program = liquidity_token_program
     )]
     pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = amm.treasury,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub treasury_account_liquidity: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     #[account(
         init_if_needed,
         payer = payer,
@@ -209,5 +241,6 @@ pub struct DepositLiquidity<'info> {
 
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
 }
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MIN_MULTI_POOL_TOKENS, MULTI_POOL_SEED, WEIGHT_ONE}, 
+    curve::deposit_amount, 
+    errors::ErrorCode, 
+    state::{Amm, CurveType, MultiPool}
+};
+
+use super::shared::{
+    amount_after_transfer_fee, 
+    amount_before_transfer_fee, 
+    mint_liquidity_tokens, 
+    multi_pool_deposit_liquidity, 
+    transfer_token
+};
+
+
+impl<'info> DepositMultiLiquidity<'info> {
+    // 剩余账户按成员顺序依次传入 [mint, 存款人代币账户, 池子金库, 代币程序]，之后是 transfer hook 需要的额外账户
+    pub fn deposit_multi_liquidity(
+        &mut self, 
+        lp_amount: u64, 
+        max_amounts: Vec<u64>, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &DepositMultiLiquidityBumps
+    ) -> Result<()> {
+        let pool = &self.multi_pool;
+        let n = pool.tokens.len();
+        require!(n >= MIN_MULTI_POOL_TOKENS, ErrorCode::InvalidTokenCount);
+        require!(
+            max_amounts.len() == n && remaining_accounts.len() >= n * 4, 
+            ErrorCode::InvalidTokenCount
+        );
+        let (token_accounts, extra_accounts) = remaining_accounts.split_at(n * 4);
+        for (token, accounts) in pool.tokens.iter().zip(token_accounts.chunks(4)) {
+            require_keys_eq!(accounts[0].key(), token.mint, ErrorCode::InvalidMint);
+            require_keys_eq!(accounts[2].key(), token.vault, ErrorCode::InvalidMint);
+            // 成员可以分别属于 SPL Token 与 Token-2022
+            require_keys_eq!(accounts[3].key(), *accounts[0].owner, ErrorCode::InvalidTokenProgram);
+        }
+
+        // 各成员代币池子实际收到的数量与用户需要转出的数量
+        let pool_creation = pool.tokens.iter().all(|token| token.reserve == 0);
+        let (amounts, inputs, liquidity) = if pool_creation {
+            if pool.curve_type == CurveType::Weighted {
+                require!(
+                    pool.tokens.iter().map(|token| token.weight).sum::<u64>() == WEIGHT_ONE, 
+                    ErrorCode::InvalidWeight
+                );
+            }
+
+            // 首笔存款按给定数量全部存入，lp_amount 作为可接受的最少流动性
+            let amounts = max_amounts
+                .iter()
+                .zip(token_accounts.chunks(4))
+                .map(|(amount, accounts)| amount_after_transfer_fee(&accounts[0], *amount))
+                .collect::<Result<Vec<_>>>()?;
+            let liquidity = multi_pool_deposit_liquidity(pool, &amounts, self.amm.fee, 0)?;
+            require!(liquidity >= pool.minimum_liquidity, ErrorCode::DepositTooSmall);
+            let liquidity = liquidity - pool.minimum_liquidity;
+            require!(liquidity >= lp_amount, ErrorCode::OutputTooSmall);
+
+            (amounts, max_amounts, liquidity)
+        } else {
+            // 按当前储备比例计算铸造 lp_amount 需要的各代币数量
+            let supply = self.mint_liquidity.supply;
+            let amounts = pool.tokens
+                .iter()
+                .map(|token| deposit_amount(lp_amount, token.reserve, supply + pool.minimum_liquidity))
+                .collect::<Vec<_>>();
+            let inputs = amounts
+                .iter()
+                .zip(token_accounts.chunks(4))
+                .map(|(amount, accounts)| amount_before_transfer_fee(&accounts[0], *amount))
+                .collect::<Result<Vec<_>>>()?;
+            require!(
+                inputs.iter().zip(&max_amounts).all(|(input, max_amount)| input <= max_amount), 
+                ErrorCode::InputTooLarge
+            );
+
+            (amounts, inputs, lp_amount)
+        };
+        require!(liquidity > 0, ErrorCode::DepositTooSmall);
+
+        for (k, accounts) in token_accounts.chunks(4).enumerate() {
+            let token = &self.multi_pool.tokens[k];
+            transfer_token(
+                accounts[1].clone(), 
+                accounts[2].clone(), 
+                accounts[0].clone(), 
+                self.deposit.to_account_info(), 
+                accounts[3].clone(), 
+                inputs[k], 
+                token.decimals, 
+                None, 
+                extra_accounts
+            )?;
+        }
+
+        for (token, amount) in self.multi_pool.tokens.iter_mut().zip(&amounts) {
+            token.reserve = token.reserve.checked_add(*amount).ok_or(error!(ErrorCode::MathOverflow))?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.multi_pool.amm.to_bytes(),
+            &self.multi_pool.id.to_bytes(),
+            MULTI_POOL_SEED.as_bytes(),
+            &[bumps.multi_pool]
+        ]];
+
+        mint_liquidity_tokens(
+            self.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.multi_pool.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            liquidity, 
+            signer_seeds
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct DepositMultiLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MULTI_POOL_SEED}, 
+    errors::ErrorCode, 
+    state::{Amm, MultiPool}
+};
+
+use super::shared::{
+    amount_after_transfer_fee, 
+    mint_liquidity_tokens, 
+    multi_pool_deposit_liquidity, 
+    transfer_token
+};
+
+
+impl<'info> DepositMultiSingleSided<'info> {
+    pub fn deposit_multi_single_sided(
+        &mut self, 
+        amount: u64, 
+        min_liquidity: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &DepositMultiSingleSidedBumps
+    ) -> Result<()> {
+        let pool = &self.multi_pool;
+        require!(pool.tokens.iter().all(|token| token.reserve > 0), ErrorCode::EmptyPool);
+        let i = pool.token_index(&self.mint.key())?;
+
+        let amount = amount.min(self.deposit_account.amount);
+
+        // 只存入一个成员代币，偏离池子比例的部分由曲线收取手续费，按扣除转账手续费后到账的数量计算
+        let received = amount_after_transfer_fee(&self.mint.to_account_info(), amount)?;
+        let mut amounts = vec![0; pool.tokens.len()];
+        amounts[i] = received;
+        let liquidity = multi_pool_deposit_liquidity(
+            pool, 
+            &amounts, 
+            self.amm.fee, 
+            self.mint_liquidity.supply + pool.minimum_liquidity
+        )?;
+        require!(liquidity > 0, ErrorCode::DepositTooSmall);
+        require!(liquidity >= min_liquidity, ErrorCode::OutputTooSmall);
+
+        transfer_token(
+            self.deposit_account.to_account_info(), 
+            self.pool_account.to_account_info(), 
+            self.mint.to_account_info(), 
+            self.deposit.to_account_info(), 
+            self.token_program.to_account_info(), 
+            amount, 
+            self.mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+
+        let token = &mut self.multi_pool.tokens[i];
+        token.reserve = token.reserve.checked_add(received).ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.multi_pool.amm.to_bytes(),
+            &self.multi_pool.id.to_bytes(),
+            MULTI_POOL_SEED.as_bytes(),
+            &[bumps.multi_pool]
+        ]];
+
+        mint_liquidity_tokens(
+            self.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.multi_pool.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            liquidity, 
+            signer_seeds
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct DepositMultiSingleSided<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = multi_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, curve::pool_curve, errors::ErrorCode, state::{Amm, Pool, PriceFeed}};
+
+use super::shared::{
+    accumulate_price, 
+    amount_after_transfer_fee, 
+    amount_before_transfer_fee, 
+    check_spot_price, 
+    mint_liquidity_tokens, 
+    mint_protocol_fee, 
+    transfer_token
+};
+
+
+impl<'info> DepositSingleSided<'info> {
+    pub fn deposit_single_sided(
+        &mut self, 
+        token: Pubkey, 
+        amount: u64, 
+        min_liquidity: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &DepositSingleSidedBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
+        require!(
+            token == self.a_mint.key() || token == self.b_mint.key(), 
+            ErrorCode::InvalidMint
+        );
+        let deposit_a = token == self.a_mint.key();
+
+        let reserve_a = self.pool.reserve_a;
+        let reserve_b = self.pool.reserve_b;
+        require!(reserve_a > 0 && reserve_b > 0, ErrorCode::EmptyPool);
+
+        // 判断金额是否正确
+        let balance = if deposit_a {
+            self.deposit_account_a.amount
+        } else {
+            self.deposit_account_b.amount
+        };
+        let amount = amount.min(balance);
+        let deposit_mint = if deposit_a { self.a_mint.to_account_info() } else { self.b_mint.to_account_info() };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        // 先按 sqrt(k) 的增长向国库铸造协议费，再按铸造后的总份额计算本次存款
+        let fee_liquidity = mint_protocol_fee(
+            &self.pool, 
+            &self.amm, 
+            &self.mint_liquidity, 
+            self.treasury_account_liquidity.as_ref().map(|account| account.to_account_info()), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            signer_seeds
+        )?;
+        let total_supply = self.mint_liquidity.supply + self.pool.minimum_liquidity + fee_liquidity;
+
+        // 启用预言机保护时，存入前后池子的价格都不能偏离参考价格太远，避免在被操纵的价格上完成内部兑换
+        let decimals = [self.a_mint.decimals, self.b_mint.decimals];
+        check_spot_price(&self.pool, self.price_feed.as_deref(), [reserve_a, reserve_b], self.amm.fee, decimals)?;
+
+        // 按池子扣除转账手续费后实际收到的数量计算存入的数量与可获得的流动性
+        let curve = pool_curve(&self.pool, [reserve_a, reserve_b], self.amm.fee, decimals)?;
+        let (total_input, liquidity) = curve.deposit_single(
+            amount_after_transfer_fee(&deposit_mint, amount)?, 
+            deposit_a, 
+            total_supply
+        )?;
+        let transfer_amount = amount_before_transfer_fee(&deposit_mint, total_input)?;
+        require!(liquidity > 0, ErrorCode::DepositTooSmall);
+        require!(liquidity >= min_liquidity, ErrorCode::OutputTooSmall);
+
+        let reserves_after = if deposit_a {
+            [reserve_a.checked_add(total_input).unwrap(), reserve_b]
+        } else {
+            [reserve_a, reserve_b.checked_add(total_input).unwrap()]
+        };
+        check_spot_price(&self.pool, self.price_feed.as_deref(), reserves_after, self.amm.fee, decimals)?;
+
+        // 将实际使用的输入存入流动池
+        if deposit_a {
+            transfer_token(
+                self.deposit_account_a.to_account_info(), 
+                self.pool_account_a.to_account_info(), 
+                self.a_mint.to_account_info(), 
+                self.deposit.to_account_info(), 
+                self.token_program.to_account_info(), 
+                transfer_amount, 
+                self.a_mint.decimals, 
+                None, 
+                remaining_accounts
+            )?;
+        } else {
+            transfer_token(
+                self.deposit_account_b.to_account_info(), 
+                self.pool_account_b.to_account_info(), 
+                self.b_mint.to_account_info(), 
+                self.deposit.to_account_info(), 
+                self.token_program.to_account_info(), 
+                transfer_amount, 
+                self.b_mint.decimals, 
+                None, 
+                remaining_accounts
+            )?;
+        }
+
+        // 单边存入只有输入侧的储备量增加
+        let pool = &mut self.pool;
+        [pool.reserve_a, pool.reserve_b] = reserves_after;
+
+        pool.update_k_last(self.amm.protocol_fee_on);
+
+        msg!("Deposited {} tokens for {} liquidity", total_input, liquidity);
+
+        mint_liquidity_tokens(
+            self.mint_liquidity.to_account_info(), 
+            self.deposit_account_liquidity.to_account_info(), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            liquidity, 
+            signer_seeds
+        )?;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct DepositSingleSided<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = amm.treasury,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub treasury_account_liquidity: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // 池子启用预言机保护时必须传入
+    pub price_feed: Option<Box<Account<'info, PriceFeed>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = a_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = b_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
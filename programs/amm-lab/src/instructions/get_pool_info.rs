@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use fixed::types::U64F64;
+
+use crate::{
+    constants::LIQUIDITY_SEED, 
+    curve::pool_curve, 
+    errors::ErrorCode, 
+    state::{Amm, Pool}
+};
+
+use super::shared::amount_before_transfer_fee;
+
+
+// get_pool_info 的返回值，价格与价值均为按代币精度换算到 UI 单位后的 U64F64 原始位
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct PoolInfo {
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    // 以 b 计价的 a 的价格与以 a 计价的 b 的价格
+    pub price_a: u128,
+    pub price_b: u128,
+    // 流动性代币总量，包含锁定的最低流动性
+    pub lp_supply: u64,
+    // 一个流动性代币分别以 a 和 b 计价的价值
+    pub lp_value_a: u128,
+    pub lp_value_b: u128,
+    // 池子总价值分别以 a 和 b 计价
+    pub tvl_a: u128,
+    pub tvl_b: u128,
+    pub fee: u16
+}
+
+
+impl<'info> GetPoolInfo<'info> {
+    pub fn get_pool_info(&self) -> Result<PoolInfo> {
+        let pool = &self.pool;
+        let mut info = PoolInfo {
+            reserve_a: pool.reserve_a,
+            reserve_b: pool.reserve_b,
+            lp_supply: self.mint_liquidity.supply + pool.minimum_liquidity,
+            fee: self.amm.fee,
+            ..Default::default()
+        };
+
+        // 空池子没有价格，其余字段保持为 0
+        if pool.reserve_a > 0 && pool.reserve_b > 0 {
+            let decimals = [self.a_mint.decimals, self.b_mint.decimals];
+            let raw_price = pool_curve(pool, [pool.reserve_a, pool.reserve_b], self.amm.fee, decimals)?.spot_price()?;
+
+            // 最小单位的价格换算成 UI 单位：乘以 10^decimals_a / 10^decimals_b
+            let price_a = if decimals[0] >= decimals[1] {
+                raw_price.checked_mul(pow10(decimals[0] - decimals[1])?)
+            } else {
+                raw_price.checked_div(pow10(decimals[1] - decimals[0])?)
+            }.ok_or(ErrorCode::MathOverflow)?;
+            let price_b = U64F64::ONE.checked_div(price_a).ok_or(ErrorCode::MathOverflow)?;
+
+            let amount_a = ui_amount(pool.reserve_a, decimals[0])?;
+            let amount_b = ui_amount(pool.reserve_b, decimals[1])?;
+            let tvl_a = amount_b.checked_mul(price_b)
+                .and_then(|value| value.checked_add(amount_a))
+                .ok_or(ErrorCode::MathOverflow)?;
+            let tvl_b = amount_a.checked_mul(price_a)
+                .and_then(|value| value.checked_add(amount_b))
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            let lp_supply = ui_amount(info.lp_supply, pool.lp_decimals)?;
+            info.price_a = price_a.to_bits();
+            info.price_b = price_b.to_bits();
+            info.tvl_a = tvl_a.to_bits();
+            info.tvl_b = tvl_b.to_bits();
+            info.lp_value_a = tvl_a.checked_div(lp_supply).ok_or(ErrorCode::MathOverflow)?.to_bits();
+            info.lp_value_b = tvl_b.checked_div(lp_supply).ok_or(ErrorCode::MathOverflow)?.to_bits();
+        }
+
+        Ok(info)
+    }
+
+    // 用户恰好到账 output 个输出代币需要转出的输入数量，包含交易费与两侧代币的转账手续费
+    // 客户端可以用它作为 swap_exact_tokens_for_tokens 的输入
+    pub fn quote_swap_in(&self, swap_a: bool, output: u64) -> Result<u64> {
+        let pool = &self.pool;
+        let (mint_in, mint_out) = if swap_a {
+            (self.a_mint.to_account_info(), self.b_mint.to_account_info())
+        } else {
+            (self.b_mint.to_account_info(), self.a_mint.to_account_info())
+        };
+
+        let curve = pool_curve(
+            pool, 
+            [pool.reserve_a, pool.reserve_b], 
+            self.amm.fee, 
+            [self.a_mint.decimals, self.b_mint.decimals]
+        )?;
+        let input = curve.swap_in(amount_before_transfer_fee(&mint_out, output)?, swap_a)?;
+
+        amount_before_transfer_fee(&mint_in, input)
+    }
+}
+
+
+// 10^exponent，精度超过 19 位时超出 u64 范围
+fn pow10(exponent: u8) -> Result<U64F64> {
+    10u64
+        .checked_pow(exponent as u32)
+        .map(U64F64::from_num)
+        .ok_or(error!(ErrorCode::MathOverflow))
+}
+
+
+fn ui_amount(amount: u64, decimals: u8) -> Result<U64F64> {
+    Ok(U64F64::from_num(amount) / pow10(decimals)?)
+}
+
+
+#[derive(Accounts)]
+pub struct GetPoolInfo<'info> {
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>
+}
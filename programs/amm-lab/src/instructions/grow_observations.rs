@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::OBSERVATION_SEED, 
+    errors::ErrorCode, 
+    state::{Observations, Pool}
+};
+
+
+impl<'info> GrowObservations<'info> {
+    // 新增的槽位为零，在环形缓冲区写到末尾之后才会启用
+    // 单次扩容受运行时 10KB 的 realloc 上限约束（约 100 个槽位），需要更大的容量时可以多次调用
+    pub fn grow_observations(&mut self, capacity: u16) -> Result<()> {
+        // 只能扩大：缩小会丢弃观测，还会把他人支付的租金退给调用者
+        let mut header = self.observations.load_mut()?;
+        require!(capacity > header.capacity, ErrorCode::InvalidObservationCapacity);
+        header.capacity = capacity;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(capacity: u16)]
+pub struct GrowObservations<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.key().as_ref(),
+            OBSERVATION_SEED.as_bytes()
+        ],
+        bump,
+        realloc = Observations::space(capacity as usize),
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub observations: AccountLoader<'info, Observations>,
+
+    pub system_program: Program<'info, System>
+}
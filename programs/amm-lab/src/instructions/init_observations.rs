@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::OBSERVATION_SEED, state::{Observation, Observations, Pool}};
+
+
+impl<'info> InitObservations<'info> {
+    pub fn init_observations(&mut self) -> Result<()> {
+        // 以池子最近一次更新的累计价格作为第一条观测
+        let pool = &mut self.pool;
+        pool.observations_enabled = true;
+
+        {
+            let mut header = self.observations.load_init()?;
+            header.pool = pool.key();
+            header.index = 0;
+            header.cardinality = 1;
+            header.capacity = 1;
+        }
+
+        let mut data = self.observations.as_ref().try_borrow_mut_data()?;
+        let (_, observations) = Observations::split_mut(&mut data);
+        observations[0] = Observation {
+            liquidity_cumulative: 0,
+            timestamp: pool.last_update_ts,
+            price_a_cumulative: pool.price_a_cumulative,
+            price_b_cumulative: pool.price_b_cumulative,
+            initialized: 1,
+            padding: [0; 7]
+        };
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct InitObservations<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Observations::space(1),
+        seeds = [
+            pool.key().as_ref(),
+            OBSERVATION_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub observations: AccountLoader<'info, Observations>,
+
+    pub system_program: Program<'info, System>
+}
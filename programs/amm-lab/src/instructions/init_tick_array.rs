@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::TICK_ARRAY_SEED, 
+    errors::ErrorCode, 
+    math::tick_math::{MAX_TICK, MIN_TICK}, 
+    state::{ClPool, TickArray}
+};
+
+
+impl<'info> InitTickArray<'info> {
+    pub fn init_tick_array(&mut self, start_tick_index: i32) -> Result<()> {
+        let tick_spacing = self.cl_pool.tick_spacing;
+        require!(
+            start_tick_index == TickArray::start_index(start_tick_index, tick_spacing)
+                && start_tick_index >= TickArray::start_index(MIN_TICK, tick_spacing)
+                && start_tick_index <= MAX_TICK,
+            ErrorCode::InvalidTickArray
+        );
+
+        let mut tick_array = self.tick_array.load_init()?;
+        tick_array.pool = self.cl_pool.key();
+        tick_array.start_tick_index = start_tick_index;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitTickArray<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TickArray>(),
+        seeds = [
+            cl_pool.key().as_ref(),
+            start_tick_index.to_le_bytes().as_ref(),
+            TICK_ARRAY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    pub system_program: Program<'info, System>
+}
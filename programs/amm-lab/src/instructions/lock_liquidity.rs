@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{constants::{LIQUIDITY_SEED, LOCK_SEED}, errors::ErrorCode, state::{LiquidityLock, Pool}};
+
+use super::shared::transfer_token;
+
+
+impl<'info> LockLiquidity<'info> {
+    pub fn lock_liquidity(&mut self, amount: u64, unlock_ts: i64, linear: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(amount > 0, ErrorCode::DepositTooSmall);
+
+        let lock = &mut self.lock;
+        require!(
+            unlock_ts > now && unlock_ts >= lock.unlock_ts, 
+            ErrorCode::InvalidUnlockTime
+        );
+
+        if lock.amount == lock.withdrawn {
+            // 新建锁定，或者之前的锁定已经全部取回
+            lock.pool = self.pool.key();
+            lock.owner = self.owner.key();
+            lock.amount = amount;
+            lock.withdrawn = 0;
+            lock.start_ts = now;
+            lock.linear = linear;
+        } else {
+            // 追加锁定只能沿用原有的释放方式并延后到期时间，释放进度不会被重置
+            require!(linear == lock.linear, ErrorCode::LockScheduleMismatch);
+            lock.amount = lock.amount.checked_add(amount).unwrap();
+        }
+        lock.unlock_ts = unlock_ts;
+
+        transfer_token(
+            self.owner_account_liquidity.to_account_info(), 
+            self.escrow_account_liquidity.to_account_info(), 
+            self.mint_liquidity.to_account_info(), 
+            self.owner.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            amount, 
+            self.mint_liquidity.decimals, 
+            None, 
+            &[]
+        )?;
+
+        msg!("Locked {} liquidity until {}", lock.amount - lock.withdrawn, unlock_ts);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct LockLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            pool.a_mint.as_ref(),
+            pool.b_mint.as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + LiquidityLock::INIT_SPACE,
+        seeds = [
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+            LOCK_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub lock: Box<Account<'info, LiquidityLock>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = owner,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub owner_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = lock,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub escrow_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{constants::MULTI_POOL_SEED, errors::ErrorCode, state::{Amm, MultiPool}};
+
+use super::shared::{
+    amount_after_transfer_fee, 
+    multi_pool_invariant, 
+    multi_pool_swap_output, 
+    transfer_token
+};
+
+
+impl<'info> MultiSwap<'info> {
+    pub fn multi_swap(
+        &mut self, 
+        input_amount: u64, 
+        min_output_amount: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &MultiSwapBumps
+    ) -> Result<()> {
+        let pool = &self.multi_pool;
+        let i = pool.token_index(&self.input_mint.key())?;
+        let j = pool.token_index(&self.output_mint.key())?;
+        require!(i != j, ErrorCode::InvalidMint);
+
+        // 按池子实际收到的数量计价，最低输出按用户实际到账的数量检查
+        let input = input_amount.min(self.trader_account_in.amount);
+        let received_input = amount_after_transfer_fee(&self.input_mint.to_account_info(), input)?;
+        let (taxed_input, output) = multi_pool_swap_output(pool, received_input, self.amm.fee, i, j)?;
+        let received_output = amount_after_transfer_fee(&self.output_mint.to_account_info(), output)?;
+        require!(output > 0 && received_output >= min_output_amount, ErrorCode::OutputTooSmall);
+
+        // 计算交易前的不变量
+        let invariant = multi_pool_invariant(pool)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.multi_pool.amm.to_bytes(),
+            &self.multi_pool.id.to_bytes(),
+            MULTI_POOL_SEED.as_bytes(),
+            &[bumps.multi_pool]
+        ]];
+
+        transfer_token(
+            self.trader_account_in.to_account_info(), 
+            self.pool_account_in.to_account_info(), 
+            self.input_mint.to_account_info(), 
+            self.trader.to_account_info(), 
+            self.input_token_program.to_account_info(), 
+            input, 
+            self.input_mint.decimals, 
+            None, 
+            remaining_accounts
+        )?;
+
+        transfer_token(
+            self.pool_account_out.to_account_info(), 
+            self.trader_account_out.to_account_info(), 
+            self.output_mint.to_account_info(), 
+            self.multi_pool.to_account_info(), 
+            self.output_token_program.to_account_info(), 
+            output, 
+            self.output_mint.decimals, 
+            Some(signer_seeds), 
+            remaining_accounts
+        )?;
+
+        msg!(
+            "Traded {} tokens ({} after fees) for {}",
+            input,
+            taxed_input,
+            received_output
+        );
+
+        // 更新内部储备量
+        let pool = &mut self.multi_pool;
+        pool.tokens[i].reserve = pool.tokens[i].reserve.checked_add(received_input).ok_or(error!(ErrorCode::MathOverflow))?;
+        pool.tokens[j].reserve = pool.tokens[j].reserve.checked_sub(output).ok_or(error!(ErrorCode::MathOverflow))?;
+
+        require!(invariant <= multi_pool_invariant(pool)?, ErrorCode::InvariantViolated);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct MultiSwap<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub trader: Signer<'info>,
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = input_mint,
+        associated_token::authority = multi_pool,
+        associated_token::token_program = input_token_program
+    )]
+    pub pool_account_in: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = output_mint,
+        associated_token::authority = multi_pool,
+        associated_token::token_program = output_token_program
+    )]
+    pub pool_account_out: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = input_mint,
+        associated_token::authority = trader,
+        associated_token::token_program = input_token_program
+    )]
+    pub trader_account_in: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = output_mint,
+        associated_token::authority = trader,
+        associated_token::token_program = output_token_program
+    )]
+    pub trader_account_out: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    // 两个成员代币可以分别属于 SPL Token 与 Token-2022
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{LIQUIDITY_SEED, OBSERVATION_SEED}, 
+    state::{Amm, Observation, Observations, Pool}
+};
+
+use super::shared::accumulate_price;
+
+
+impl<'info> Observe<'info> {
+    // 返回每个 seconds_ago 对应时刻的累计值，调用方用两个时刻的差值除以时间间隔得到均值
+    pub fn observe(&self, seconds_ago: Vec<u32>) -> Result<Vec<Observation>> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // 在池子的副本上把累计价格推进到当前时刻
+        let mut pool = (**self.pool).clone();
+        accumulate_price(&mut pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
+        let data = self.observations.as_ref().try_borrow_data()?;
+        let (header, observations) = Observations::split(&data);
+        let latest = header.latest(observations);
+        let liquidity = self.mint_liquidity.supply + pool.minimum_liquidity;
+        let current = Observation {
+            liquidity_cumulative: latest.liquidity_cumulative
+                .wrapping_add(liquidity as u128 * (now - latest.timestamp) as u128),
+            timestamp: now,
+            price_a_cumulative: pool.price_a_cumulative,
+            price_b_cumulative: pool.price_b_cumulative,
+            initialized: 1,
+            padding: [0; 7]
+        };
+
+        seconds_ago
+            .iter()
+            .map(|&seconds| header.observe_at(observations, now - seconds as i64, &current))
+            .collect()
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct Observe<'info> {
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            pool.key().as_ref(),
+            OBSERVATION_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub observations: AccountLoader<'info, Observations>
+}
@@ -0,0 +1,227 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::{set_authority, spl_token_2022::instruction::AuthorityType, SetAuthority, Token2022},
+    token_interface::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_field, 
+        Mint, TokenAccount, TokenMetadataInitialize, TokenMetadataUpdateField
+    }
+};
+
+use crate::{
+    constants::{CL_POOL_SEED, POSITION_SEED}, 
+    errors::ErrorCode, 
+    math::tick_math::{MAX_TICK, MIN_TICK}, 
+    state::{Amm, ClPool, Position}
+};
+
+use super::shared::{mint_liquidity_tokens, mint_symbol};
+
+
+impl<'info> OpenPosition<'info> {
+    pub fn open_position(
+        &mut self, 
+        tick_lower: i32, 
+        tick_upper: i32, 
+        bumps: &OpenPositionBumps
+    ) -> Result<()> {
+        let tick_spacing = self.cl_pool.tick_spacing as i32;
+        require!(
+            tick_lower < tick_upper
+                && tick_lower >= MIN_TICK
+                && tick_upper <= MAX_TICK
+                && tick_lower % tick_spacing == 0
+                && tick_upper % tick_spacing == 0,
+            ErrorCode::InvalidTick
+        );
+
+        let position = &mut self.position;
+        position.pool = self.cl_pool.key();
+        position.position_mint = self.position_mint.key();
+        position.tick_lower = tick_lower;
+        position.tick_upper = tick_upper;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.cl_pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            CL_POOL_SEED.as_bytes(),
+            &[bumps.cl_pool]
+        ]];
+
+        self.initialize_position_metadata(tick_lower, tick_upper, signer_seeds)?;
+
+        // 铸造唯一的仓位 NFT 后移除铸币权限
+        mint_liquidity_tokens(
+            self.position_mint.to_account_info(), 
+            self.owner_position_account.to_account_info(), 
+            self.cl_pool.to_account_info(), 
+            self.position_token_program.to_account_info(), 
+            1, 
+            signer_seeds
+        )?;
+
+        set_authority(
+            CpiContext::new_with_signer(
+                self.position_token_program.to_account_info(), 
+                SetAuthority { 
+                    current_authority: self.cl_pool.to_account_info(), 
+                    account_or_mint: self.position_mint.to_account_info() 
+                }, 
+                signer_seeds
+            ), 
+            AuthorityType::MintTokens, 
+            None
+        )
+    }
+
+    // 仓位 NFT 的元数据展示交易对和价格区间，例如 "SOL-USDC CL Position"，简称取自两种代币自身的元数据
+    fn initialize_position_metadata(
+        &mut self, 
+        tick_lower: i32, 
+        tick_upper: i32, 
+        signer_seeds: &[&[&[u8]]]
+    ) -> Result<()> {
+        let symbol_a = mint_symbol(&self.a_mint.to_account_info())?;
+        let symbol_b = mint_symbol(&self.b_mint.to_account_info())?;
+        let name = format!("{}-{} CL Position", symbol_a, symbol_b);
+        let symbol = format!("{}-{}-POS", symbol_a, symbol_b);
+        let uri = self.amm.lp_metadata_uri.clone();
+        let range = [
+            ("tick_lower".to_string(), tick_lower.to_string()),
+            ("tick_upper".to_string(), tick_upper.to_string())
+        ];
+
+        // 元数据写在 mint 账户内，需要先补足租金
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(self.cl_pool.key()))?,
+            mint: self.position_mint.key(),
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: range.to_vec()
+        };
+        let mint_info = self.position_mint.to_account_info();
+        let space = mint_info.data_len() + metadata.tlv_size_of()?;
+        let lamports = Rent::get()?.minimum_balance(space).saturating_sub(mint_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(), 
+                    Transfer { 
+                        from: self.payer.to_account_info(), 
+                        to: mint_info.clone() 
+                    }
+                ), 
+                lamports
+            )?;
+        }
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.position_token_program.to_account_info(), 
+                TokenMetadataInitialize { 
+                    program_id: self.position_token_program.to_account_info(), 
+                    metadata: mint_info.clone(), 
+                    update_authority: self.cl_pool.to_account_info(), 
+                    mint_authority: self.cl_pool.to_account_info(), 
+                    mint: mint_info.clone() 
+                }, 
+                signer_seeds
+            ), 
+            name, 
+            symbol, 
+            uri
+        )?;
+
+        for (key, value) in range {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.position_token_program.to_account_info(), 
+                    TokenMetadataUpdateField { 
+                        program_id: self.position_token_program.to_account_info(), 
+                        metadata: mint_info.clone(), 
+                        update_authority: self.cl_pool.to_account_info() 
+                    }, 
+                    signer_seeds
+                ), 
+                Field::Key(key), 
+                value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Receives the position NFT
+    pub owner: UncheckedAccount<'info>,
+
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            CL_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub cl_pool: Box<Account<'info, ClPool>>,
+
+    // 仓位 NFT 由新的密钥对创建，池子同时作为铸币和元数据权限
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = cl_pool,
+        mint::token_program = position_token_program,
+        extensions::metadata_pointer::authority = cl_pool,
+        extensions::metadata_pointer::metadata_address = position_mint
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = position_token_program
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub position_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP, MIN_RAMP_DURATION}, 
+    errors::ErrorCode, 
+    state::{Amm, CurveType, Pool}
+};
+
+
+impl<'info> RampAmp<'info> {
+    pub fn ramp_amp(&mut self, target_amp: u64, ramp_stop_ts: i64) -> Result<()> {
+        let pool = &mut self.pool;
+        require!(pool.curve_type == CurveType::Stable, ErrorCode::NotStablePool);
+        require!((MIN_AMP..=MAX_AMP).contains(&target_amp), ErrorCode::InvalidAmp);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= pool.ramp_stop_ts && ramp_stop_ts >= now + MIN_RAMP_DURATION,
+            ErrorCode::InvalidRamp
+        );
+
+        // 限制单次调整幅度，避免放大系数突变被套利
+        let current_amp = pool.current_amp(now);
+        require!(
+            target_amp <= current_amp * MAX_AMP_CHANGE && target_amp * MAX_AMP_CHANGE >= current_amp,
+            ErrorCode::InvalidAmp
+        );
+
+        pool.initial_amp = current_amp;
+        pool.target_amp = target_amp;
+        pool.ramp_start_ts = now;
+        pool.ramp_stop_ts = ramp_stop_ts;
+
+        Ok(())
+    }
+
+    // 立即停止调整，放大系数固定在当前值
+    pub fn stop_ramp_amp(&mut self) -> Result<()> {
+        let pool = &mut self.pool;
+        require!(pool.curve_type == CurveType::Stable, ErrorCode::NotStablePool);
+
+        let now = Clock::get()?.unix_timestamp;
+        let current_amp = pool.current_amp(now);
+        pool.initial_amp = current_amp;
+        pool.target_amp = current_amp;
+        pool.ramp_start_ts = now;
+        pool.ramp_stop_ts = now;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct RampAmp<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        has_one = amm
+    )]
+    pub pool: Box<Account<'info, Pool>>
+}
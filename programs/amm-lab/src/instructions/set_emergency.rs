@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Amm, Pool};
+
+use super::shared::accumulate_price;
+
+
+impl<'info> SetEmergency<'info> {
+    // 池子暂停或曲线出现问题时，管理员开启紧急状态让 LP 按比例退出
+    pub fn set_emergency(&mut self, emergency: bool) -> Result<()> {
+        if !self.pool.emergency {
+            // 紧急状态下不再累计价格，进入之前先把价格累计到当前时刻
+            accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+        } else if !emergency {
+            // 紧急期间的价格不计入累计值，退出时从当前时刻重新开始累计
+            self.pool.last_update_ts = Clock::get()?.unix_timestamp;
+        }
+        self.pool.emergency = emergency;
+
+        msg!("Pool emergency mode set to {}", emergency);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetEmergency<'info> {
+    pub admin: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub pool: Box<Account<'info, Pool>>
+}
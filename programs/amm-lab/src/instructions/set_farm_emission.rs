@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::FARM_SEED, errors::ErrorCode, state::{Amm, Farm, Pool}};
+
+
+impl<'info> SetFarmEmission<'info> {
+    pub fn set_farm_emission(&mut self, index: u8, emission_per_second: u64) -> Result<()> {
+        let farm = &mut self.farm;
+        require!((index as usize) < farm.rewards.len(), ErrorCode::InvalidReward);
+
+        // 先按旧的速率结算到当前时间
+        farm.update_rewards(Clock::get()?.unix_timestamp);
+        farm.rewards[index as usize].emission_per_second = emission_per_second;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetFarmEmission<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(has_one = amm)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.key().as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump,
+        has_one = pool
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+}
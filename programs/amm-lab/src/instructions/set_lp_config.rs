@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::{MAX_LP_DECIMALS, MINIMUM_LIQUIDITY}, errors::ErrorCode, state::Amm};
+
+
+impl<'info> SetLpConfig<'info> {
+    pub fn set_lp_config(&mut self, lp_decimals: u8, minimum_liquidity: u64) -> Result<()> {
+        require!(
+            lp_decimals <= MAX_LP_DECIMALS && minimum_liquidity >= MINIMUM_LIQUIDITY, 
+            ErrorCode::InvalidLpConfig
+        );
+
+        // 只影响之后创建的池子，已有池子沿用创建时记录的配置
+        let amm = &mut self.amm;
+        amm.lp_decimals = lp_decimals;
+        amm.minimum_liquidity = minimum_liquidity;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetLpConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_URI_LEN, errors::ErrorCode, state::Amm};
+
+
+impl<'info> SetLpMetadataUri<'info> {
+    pub fn set_lp_metadata_uri(&mut self, uri: String) -> Result<()> {
+        require!(uri.len() <= MAX_URI_LEN, ErrorCode::InvalidMetadata);
+
+        self.amm.lp_metadata_uri = uri;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetLpMetadataUri<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, state::{Amm, Pool, PriceFeed}};
+
+
+impl<'info> SetPriceFeed<'info> {
+    // 不传入 price_feed 时关闭该池子的预言机保护
+    pub fn set_price_feed(&mut self, max_price_deviation: u16, max_price_age: i64) -> Result<()> {
+        let pool = &mut self.pool;
+        match &self.price_feed {
+            Some(price_feed) => {
+                require!(
+                    max_price_deviation < 10000 && max_price_age > 0, 
+                    ErrorCode::InvalidPriceFeed
+                );
+                pool.price_feed = price_feed.key();
+                pool.max_price_deviation = max_price_deviation;
+                pool.max_price_age = max_price_age;
+            }
+            None => {
+                pool.price_feed = Pubkey::default();
+                pool.max_price_deviation = 0;
+                pool.max_price_age = 0;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        has_one = amm
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub price_feed: Option<Box<Account<'info, PriceFeed>>>
+}
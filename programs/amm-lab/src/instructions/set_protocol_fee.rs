@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Amm;
+
+
+impl<'info> SetProtocolFee<'info> {
+    // 开启后各池在下次存取流动性时才开始记录 k_last，开启前的增长不收取协议费
+    pub fn set_protocol_fee(&mut self, protocol_fee_on: bool, treasury: Pubkey) -> Result<()> {
+        let amm = &mut self.amm;
+        amm.protocol_fee_on = protocol_fee_on;
+        amm.treasury = treasury;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_SUM_RATE_CHANGE_BPS, MIN_RAMP_DURATION}, 
+    errors::ErrorCode, 
+    state::{Amm, CurveType, Pool}
+};
+
+
+impl<'info> SetSumRate<'info> {
+    // 锚定资产的兑换比例发生变化时由管理员更新，新比例在 ramp_stop_ts 之前逐步生效
+    pub fn set_sum_rate(&mut self, sum_rate: u64, ramp_stop_ts: i64) -> Result<()> {
+        let pool = &mut self.pool;
+        require!(pool.curve_type == CurveType::ConstantSum, ErrorCode::NotConstantSumPool);
+        require!(sum_rate > 0, ErrorCode::InvalidRate);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= pool.ramp_stop_ts && ramp_stop_ts >= now + MIN_RAMP_DURATION,
+            ErrorCode::InvalidRamp
+        );
+
+        // 限制单次调整幅度，避免兑换比例突变被套利
+        let current_rate = pool.current_sum_rate(now);
+        require!(
+            sum_rate.abs_diff(current_rate) as u128 * 10000 
+                <= current_rate as u128 * MAX_SUM_RATE_CHANGE_BPS as u128,
+            ErrorCode::InvalidRate
+        );
+
+        pool.initial_sum_rate = current_rate;
+        pool.sum_rate = sum_rate;
+        pool.ramp_start_ts = now;
+        pool.ramp_stop_ts = ramp_stop_ts;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct SetSumRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        has_one = amm
+    )]
+    pub pool: Box<Account<'info, Pool>>
+}
@@ -1,6 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{TransferChecked, transfer_checked};
+use anchor_spl::{
+    token_2022::{
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions}, 
+            onchain::invoke_transfer_checked, 
+            state::Mint as MintState
+        }, 
+        Token2022
+    }, 
+    token_interface::{
+        spl_token_metadata_interface::state::TokenMetadata, 
+        transfer_checked, 
+        Mint, 
+        MintTo, 
+        TransferChecked, 
+        mint_to
+    }
+};
+use fixed::types::U64F64;
 
+use crate::{
+    constants::{MAX_SYMBOL_LEN, MINT_LABEL_LEN}, 
+    curve::{
+        pool_curve, 
+        stable::{stable_deposit_liquidity, stable_invariant, stable_rates, stable_swap_output, stable_withdraw_single}, 
+        weighted::{weighted_deposit_liquidity, weighted_invariant, weighted_swap_output, weighted_withdraw_single}
+    }, 
+    math::{
+        big_num::{to_u64, U256}, 
+        liquidity_math::add_delta, 
+        sqrt_price_math::{get_amount_a_delta, get_amount_b_delta}, 
+        tick_math::get_sqrt_price_at_tick
+    }, 
+    errors::ErrorCode, 
+    state::{fee_growth_inside, Amm, ClPool, CurveType, MultiPool, Pool, Position, PriceFeed, Tick, TickArray}
+};
 
 
 pub fn transfer_token<'info>(
@@ -11,8 +45,30 @@ pub fn transfer_token<'info>(
     token_program: AccountInfo<'info>,
     amount: u64,
     decimals: u8,
-    signer_seeds: Option<&[&[&[u8]]]>
+    signer_seeds: Option<&[&[&[u8]]]>,
+    extra_accounts: &[AccountInfo<'info>]
 ) -> Result<()> {
+    // Token-2022 代币可能带有 transfer hook，从 extra_accounts 中找出 hook 需要的额外账户一并传入
+    // 客户端按 mint 的 ExtraAccountMetaList 解析出每一笔转账需要的账户，拼接后作为剩余账户传入
+    // 剩余账户的顺序：deposit_multi_liquidity / withdraw_multi_liquidity 先按池内代币顺序放每个代币的
+    // [mint, 用户账户, 金库, 代币程序]，其余指令没有这一段；
+    // 之后是所有转账的 hook 账户（hook 程序、校验账户与解析出的账户），按地址查找，彼此顺序不限，重复的只需传一次
+    // 参见 sdk/transfer-hook.ts
+    if *token_program.key == Token2022::id() {
+        invoke_transfer_checked(
+            token_program.key, 
+            from, 
+            mint, 
+            to, 
+            authority, 
+            extra_accounts, 
+            amount, 
+            decimals, 
+            signer_seeds.unwrap_or_default()
+        )?;
+        return Ok(());
+    }
+
     let accounts = TransferChecked {
         from,
         to,
@@ -31,3 +87,370 @@ pub fn transfer_token<'info>(
 
     transfer_checked(cpi_ctx, amount, decimals)
 }
+
+
+// Token-2022 代币的转账手续费配置，普通代币或未启用该扩展时返回 None
+fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != Token2022::id() {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+
+// 转出 amount 后收款方实际到账的数量
+pub fn amount_after_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(amount);
+    };
+
+    let fee = config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    Ok(amount - fee)
+}
+
+
+// 收款方恰好到账 amount 时需要转出的数量
+pub fn amount_before_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint)? else {
+        return Ok(amount);
+    };
+
+    let fee = config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    amount.checked_add(fee).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+
+// 代币在 LP 名称中使用的简称：只信任写在 mint 账户内的 Token-2022 元数据，
+// 没有元数据或简称不合法时退回到 mint 地址的前几位
+pub fn mint_symbol(mint: &AccountInfo) -> Result<String> {
+    if *mint.owner == Token2022::id() {
+        let data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+        if let Ok(metadata) = mint_state.get_variable_len_extension::<TokenMetadata>() {
+            let symbol = metadata.symbol.trim();
+            if metadata.mint == *mint.key 
+                && !symbol.is_empty() 
+                && symbol.len() <= MAX_SYMBOL_LEN 
+                && symbol.chars().all(|c| c.is_ascii_alphanumeric()) 
+            {
+                return Ok(symbol.to_string());
+            }
+        }
+    }
+
+    Ok(mint.key.to_string()[..MINT_LABEL_LEN].to_string())
+}
+
+
+pub fn mint_liquidity_tokens<'info>(
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()> {
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program, 
+            MintTo { mint, to, authority }, 
+            signer_seeds
+        ),
+        amount
+    )
+}
+
+
+// 在储备量变化之前，按上次更新以来经过的秒数累计两侧的边际价格
+pub fn accumulate_price(pool: &mut Pool, fee: u16, decimals: [u8; 2]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let elapsed = now.saturating_sub(pool.last_update_ts);
+
+    if elapsed > 0 && pool.reserve_a > 0 && pool.reserve_b > 0 {
+        let price_a = pool_curve(pool, [pool.reserve_a, pool.reserve_b], fee, decimals)?.spot_price()?;
+        let price_b = U64F64::ONE.checked_div(price_a).unwrap_or(U64F64::MAX);
+
+        let accumulate = |cumulative: [u64; 4], price: U64F64| {
+            let (sum, _) = U256(cumulative).overflowing_add(U256::from(price.to_bits()) * U256::from(elapsed));
+            sum.0
+        };
+        pool.price_a_cumulative = accumulate(pool.price_a_cumulative, price_a);
+        pool.price_b_cumulative = accumulate(pool.price_b_cumulative, price_b);
+    }
+    pool.last_update_ts = now;
+
+    Ok(())
+}
+
+
+// 池子启用预言机保护时返回校验过的参考价格账户
+fn enabled_price_feed<'a>(pool: &Pool, price_feed: Option<&'a Account<PriceFeed>>) -> Result<Option<&'a PriceFeed>> {
+    if pool.price_feed == Pubkey::default() {
+        return Ok(None);
+    }
+
+    let price_feed = price_feed.ok_or(ErrorCode::InvalidPriceFeed)?;
+    require_keys_eq!(price_feed.key(), pool.price_feed, ErrorCode::InvalidPriceFeed);
+
+    Ok(Some(price_feed))
+}
+
+
+// 以 a_amount 个 a 与 b_amount 个 b 在池内成交时，成交价不能偏离参考价格太远
+pub fn check_trade_price(pool: &Pool, price_feed: Option<&Account<PriceFeed>>, a_amount: u64, b_amount: u64) -> Result<()> {
+    if let Some(price_feed) = enabled_price_feed(pool, price_feed)? {
+        price_feed.check_price(
+            a_amount, 
+            b_amount, 
+            pool.max_price_deviation, 
+            pool.max_price_age, 
+            pool.last_update_ts
+        )?;
+    }
+
+    Ok(())
+}
+
+
+// 储备量为 reserves 时池子的边际价格不能偏离参考价格太远
+pub fn check_spot_price(
+    pool: &Pool, 
+    price_feed: Option<&Account<PriceFeed>>, 
+    reserves: [u64; 2], 
+    fee: u16, 
+    decimals: [u8; 2]
+) -> Result<()> {
+    if let Some(price_feed) = enabled_price_feed(pool, price_feed)? {
+        let spot_price = pool_curve(pool, reserves, fee, decimals)?.spot_price()?;
+        price_feed.check_spot_price(
+            spot_price.to_bits(), 
+            pool.max_price_deviation, 
+            pool.max_price_age, 
+            pool.last_update_ts
+        )?;
+    }
+
+    Ok(())
+}
+
+
+// 协议费开启时按 sqrt(k) 的增长向国库铸造流动性，返回铸造的数量
+pub fn mint_protocol_fee<'info>(
+    pool: &Pool,
+    amm: &Amm,
+    mint: &InterfaceAccount<'info, Mint>,
+    treasury_account: Option<AccountInfo<'info>>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<u64> {
+    if !amm.protocol_fee_on {
+        return Ok(0);
+    }
+
+    let fee_liquidity = pool.protocol_fee_liquidity(mint.supply + pool.minimum_liquidity)?;
+    if fee_liquidity > 0 {
+        let treasury_account = treasury_account.ok_or(error!(ErrorCode::MissingTreasuryAccount))?;
+        mint_liquidity_tokens(
+            mint.to_account_info(), 
+            treasury_account, 
+            authority, 
+            token_program, 
+            fee_liquidity, 
+            signer_seeds
+        )?;
+    }
+
+    Ok(fee_liquidity)
+}
+
+
+// 多资产池按曲线计算的不变量
+pub fn multi_pool_invariant(pool: &MultiPool) -> Result<u128> {
+    match pool.curve_type {
+        CurveType::Stable => stable_invariant(pool.amp, &pool.reserves(), &stable_rates(&pool.decimals())),
+        CurveType::Weighted => weighted_invariant(&pool.reserves(), &pool.weights()),
+        CurveType::ConstantProduct | CurveType::ConstantSum => err!(ErrorCode::UnsupportedCurve)
+    }
+}
+
+
+// 多资产池中以第 i 个代币兑换第 j 个代币的输出
+pub fn multi_pool_swap_output(pool: &MultiPool, input: u64, fee: u16, i: usize, j: usize) -> Result<(u64, u64)> {
+    let reserves = pool.reserves();
+    match pool.curve_type {
+        CurveType::Stable => {
+            stable_swap_output(input, fee, pool.amp, &reserves, &stable_rates(&pool.decimals()), i, j)
+        },
+        CurveType::Weighted => {
+            let weights = pool.weights();
+            weighted_swap_output(input, fee, [reserves[i], reserves[j]], [weights[i], weights[j]])
+        },
+        CurveType::ConstantProduct | CurveType::ConstantSum => err!(ErrorCode::UnsupportedCurve)
+    }
+}
+
+
+// 向多资产池存入任意组合的代币可获得的流动性
+pub fn multi_pool_deposit_liquidity(pool: &MultiPool, amounts: &[u64], fee: u16, total_supply: u64) -> Result<u64> {
+    let reserves = pool.reserves();
+    match pool.curve_type {
+        CurveType::Stable => stable_deposit_liquidity(
+            amounts, 
+            fee, 
+            pool.amp, 
+            &reserves, 
+            &stable_rates(&pool.decimals()), 
+            total_supply
+        ),
+        CurveType::Weighted => weighted_deposit_liquidity(amounts, fee, &reserves, &pool.weights(), total_supply),
+        CurveType::ConstantProduct | CurveType::ConstantSum => err!(ErrorCode::UnsupportedCurve)
+    }
+}
+
+
+// 从多资产池赎回流动性并全部换成第 i 个代币
+pub fn multi_pool_withdraw_single(pool: &MultiPool, amount: u64, fee: u16, i: usize, total_supply: u64) -> Result<u64> {
+    match pool.curve_type {
+        CurveType::Stable => stable_withdraw_single(
+            amount, 
+            fee, 
+            pool.amp, 
+            &pool.reserves(), 
+            &stable_rates(&pool.decimals()), 
+            i, 
+            total_supply
+        ),
+        CurveType::Weighted => {
+            let token = &pool.tokens[i];
+            weighted_withdraw_single(amount, fee, token.reserve, token.weight, total_supply)
+        },
+        CurveType::ConstantProduct | CurveType::ConstantSum => err!(ErrorCode::UnsupportedCurve)
+    }
+}
+
+
+// 修改集中流动性仓位，返回增加时需要存入、减少时可以取回的两侧数量
+pub fn modify_cl_position(
+    pool: &mut ClPool, 
+    position: &mut Position, 
+    lower: &mut Tick, 
+    upper: &mut Tick, 
+    liquidity_delta: i128
+) -> Result<(u64, u64)> {
+    let tick_lower = position.tick_lower;
+    let tick_upper = position.tick_upper;
+
+    let (mut flipped_lower, mut flipped_upper) = (false, false);
+    if liquidity_delta != 0 {
+        flipped_lower = lower.update(
+            tick_lower, 
+            pool.tick_current, 
+            liquidity_delta, 
+            pool.fee_growth_global_a_x64, 
+            pool.fee_growth_global_b_x64, 
+            false
+        )?;
+        flipped_upper = upper.update(
+            tick_upper, 
+            pool.tick_current, 
+            liquidity_delta, 
+            pool.fee_growth_global_a_x64, 
+            pool.fee_growth_global_b_x64, 
+            true
+        )?;
+    }
+
+    // 先按旧的流动性结算手续费
+    let (inside_a, inside_b) = fee_growth_inside(
+        lower, 
+        upper, 
+        tick_lower, 
+        tick_upper, 
+        pool.tick_current, 
+        pool.fee_growth_global_a_x64, 
+        pool.fee_growth_global_b_x64
+    );
+    position.update(liquidity_delta, inside_a, inside_b)?;
+
+    // 不再被任何仓位引用的 tick 清空
+    if liquidity_delta < 0 {
+        if flipped_lower {
+            lower.clear();
+        }
+        if flipped_upper {
+            upper.clear();
+        }
+    }
+
+    if liquidity_delta == 0 {
+        return Ok((0, 0));
+    }
+
+    // 增加流动性时向上取整，减少时向下取整，都对池子有利
+    let round_up = liquidity_delta > 0;
+    let liquidity = liquidity_delta.unsigned_abs();
+    let sqrt_price_lower = get_sqrt_price_at_tick(tick_lower)?;
+    let sqrt_price_upper = get_sqrt_price_at_tick(tick_upper)?;
+
+    let (amount_a, amount_b) = if pool.tick_current < tick_lower {
+        (
+            get_amount_a_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?,
+            0
+        )
+    } else if pool.tick_current < tick_upper {
+        // 当前价格在区间内，同时改变活跃流动性
+        pool.liquidity = add_delta(pool.liquidity, liquidity_delta)?;
+        (
+            get_amount_a_delta(pool.sqrt_price_x64, sqrt_price_upper, liquidity, round_up)?,
+            get_amount_b_delta(sqrt_price_lower, pool.sqrt_price_x64, liquidity, round_up)?
+        )
+    } else {
+        (
+            0,
+            get_amount_b_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?
+        )
+    };
+
+    Ok((to_u64(amount_a)?, to_u64(amount_b)?))
+}
+
+
+// 取出仓位上下边界的 tick 交给 f 处理，两个 tick 可能位于同一个数组中
+pub fn with_position_ticks<'info, T>(
+    tick_array_lower: &AccountLoader<'info, TickArray>,
+    tick_array_upper: &AccountLoader<'info, TickArray>,
+    pool: Pubkey,
+    tick_spacing: u16,
+    tick_lower: i32,
+    tick_upper: i32,
+    f: impl FnOnce(&mut Tick, &mut Tick) -> Result<T>
+) -> Result<T> {
+    if tick_array_lower.key() == tick_array_upper.key() {
+        let mut array = tick_array_lower.load_mut()?;
+        require_keys_eq!(array.pool, pool, ErrorCode::InvalidTickArray);
+
+        let lower_offset = array.tick_offset(tick_lower, tick_spacing)?;
+        let upper_offset = array.tick_offset(tick_upper, tick_spacing)?;
+        let (left, right) = array.ticks.split_at_mut(upper_offset);
+
+        f(&mut left[lower_offset], &mut right[0])
+    } else {
+        let mut lower_array = tick_array_lower.load_mut()?;
+        let mut upper_array = tick_array_upper.load_mut()?;
+        require_keys_eq!(lower_array.pool, pool, ErrorCode::InvalidTickArray);
+        require_keys_eq!(upper_array.pool, pool, ErrorCode::InvalidTickArray);
+
+        let lower_offset = lower_array.tick_offset(tick_lower, tick_spacing)?;
+        let upper_offset = upper_array.tick_offset(tick_upper, tick_spacing)?;
+
+        f(&mut lower_array.ticks[lower_offset], &mut upper_array.ticks[upper_offset])
+    }
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::AUTHORITY_SEED, errors::ErrorCode, state::Pool};
+
+use super::shared::transfer_token;
+
+
+impl<'info> Skim<'info> {
+    pub fn skim(&mut self, remaining_accounts: &[AccountInfo<'info>], bumps: &SkimBumps) -> Result<()> {
+        // 金库中超出储备量的部分
+        let excess_a = self.pool_account_a.amount.saturating_sub(self.pool.reserve_a);
+        let excess_b = self.pool_account_b.amount.saturating_sub(self.pool.reserve_b);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        if excess_a > 0 {
+            transfer_token(
+                self.pool_account_a.to_account_info(), 
+                self.recipient_account_a.to_account_info(), 
+                self.a_mint.to_account_info(), 
+                self.pool_authority.to_account_info(), 
+                self.token_program.to_account_info(), 
+                excess_a, 
+                self.a_mint.decimals, 
+                Some(signer_seeds), 
+                remaining_accounts
+            )?;
+        }
+
+        if excess_b > 0 {
+            transfer_token(
+                self.pool_account_b.to_account_info(), 
+                self.recipient_account_b.to_account_info(), 
+                self.b_mint.to_account_info(), 
+                self.pool_authority.to_account_info(), 
+                self.token_program.to_account_info(), 
+                excess_b, 
+                self.b_mint.decimals, 
+                Some(signer_seeds), 
+                remaining_accounts
+            )?;
+        }
+
+        msg!("Skimmed {} and {}", excess_a, excess_b);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct Skim<'info> {
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = a_mint,
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = a_mint,
+        token::token_program = token_program
+    )]
+    pub recipient_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = b_mint,
+        token::token_program = token_program
+    )]
+    pub recipient_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
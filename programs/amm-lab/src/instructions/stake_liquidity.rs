@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{constants::{FARM_SEED, STAKE_SEED}, errors::ErrorCode, state::{Farm, UserStake}};
+
+use super::shared::transfer_token;
+
+
+impl<'info> StakeLiquidity<'info> {
+    pub fn stake_liquidity(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::DepositTooSmall);
+
+        let farm = &mut self.farm;
+        farm.update_rewards(Clock::get()?.unix_timestamp);
+
+        let stake = &mut self.stake;
+        stake.farm = farm.key();
+        stake.owner = self.owner.key();
+        stake.settle(farm);
+        stake.amount = stake.amount.checked_add(amount).unwrap();
+        stake.reset_debts(farm);
+
+        farm.total_staked = farm.total_staked.checked_add(amount).unwrap();
+
+        transfer_token(
+            self.owner_account_liquidity.to_account_info(), 
+            self.farm_account_liquidity.to_account_info(), 
+            self.mint_liquidity.to_account_info(), 
+            self.owner.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            amount, 
+            self.mint_liquidity.decimals, 
+            None, 
+            &[]
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct StakeLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            farm.pool.as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump,
+        has_one = mint_liquidity
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserStake::INIT_SPACE,
+        seeds = [
+            farm.key().as_ref(),
+            owner.key().as_ref(),
+            STAKE_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub stake: Box<Account<'info, UserStake>>,
+
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = owner,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub owner_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = farm,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub farm_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
-use fixed::types::I64F64;
 
-use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, errors::ErrorCode, instructions::shared::transfer_token, state::{Amm, Pool}};
+use crate::{
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, OBSERVATION_SEED}, 
+    curve::{pool_curve, Curve}, 
+    errors::ErrorCode, 
+    instructions::shared::{accumulate_price, amount_after_transfer_fee, check_trade_price, transfer_token}, 
+    state::{Amm, Observations, Pool, PriceFeed}
+};
 
 
 impl<'info> SwapExactTokensForTokens<'info> {
@@ -11,6 +16,7 @@ impl<'info> SwapExactTokensForTokens<'info> {
         swap_a: bool,
         input_amount: u64,
         min_output_amount: u64,
+        remaining_accounts: &[AccountInfo<'info>], 
         bumps: &SwapExactTokensForTokensBumps
     ) -> Result<()> {
         let input = if swap_a && input_amount > self.trader_account_a.amount {
@@ -21,44 +27,77 @@ impl<'info> SwapExactTokensForTokens<'info> {
             input_amount
         };
 
-        // 计算交易费，用于计算输出
-        let taxed_input = input - input * self.amm.fee as u64 / 10000;
-
-        let pool_a = &self.pool_account_a;
-        let pool_b = &self.pool_account_b;
-        let output = if swap_a {
-            I64F64::from_num(taxed_input)
-                .checked_mul(I64F64::from_num(pool_b.amount))
-                .unwrap()
-                .checked_div(
-                    I64F64::from_num(pool_a.amount)
-                        .checked_add(I64F64::from_num(taxed_input))
-                        .unwrap()
-                )
-                .unwrap()
-                .to_num::<u64>()
-        } else {
-            I64F64::from_num(taxed_input)
-                .checked_mul(I64F64::from_num(pool_a.amount))
-                .unwrap()
-                .checked_div(
-                    I64F64::from_num(pool_b.amount)
-                        .checked_add(I64F64::from_num(taxed_input))
-                        .unwrap()
-                    )
-                .unwrap()
-                .to_num::<u64>()
-        };
+        // 按池子实际收到的数量计价，用户实际到账的数量还要扣除输出代币的转账手续费
+        let (mint_in, mint_out) = self.mints(swap_a);
+        let received_input = amount_after_transfer_fee(&mint_in, input)?;
+        let (taxed_input, output) = self.curve()?.swap_out(received_input, swap_a)?;
+        let received_output = amount_after_transfer_fee(&mint_out, output)?;
+        require!(received_output >= min_output_amount, ErrorCode::OutputTooSmall);
+
+        self.swap(swap_a, input, received_input, output, remaining_accounts, bumps)?;
+
+        msg!(
+            "Traded {} tokens ({} after fees) for {}",
+            input,
+            taxed_input,
+            received_output
+        );
+
+        Ok(())
+    }
+
+    // 按方向返回 (输入代币, 输出代币)
+    fn mints(&self, swap_a: bool) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        let (a_mint, b_mint) = (self.a_mint.to_account_info(), self.b_mint.to_account_info());
+        if swap_a { (a_mint, b_mint) } else { (b_mint, a_mint) }
+    }
+
+    fn curve(&self) -> Result<Box<dyn Curve>> {
+        pool_curve(
+            &self.pool, 
+            [self.pool.reserve_a, self.pool.reserve_b], 
+            self.amm.fee, 
+            [self.a_mint.decimals, self.b_mint.decimals]
+        )
+    }
+
+    // 完成兑换的转账并更新储备量，兑换后曲线的不变量不能减小
+    // input 为用户转出的数量，received_input 为扣除转账手续费后池子实际收到的数量
+    // remaining_accounts 为两侧代币 transfer hook 需要的额外账户
+    fn swap(
+        &mut self, 
+        swap_a: bool, 
+        input: u64, 
+        received_input: u64, 
+        output: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &SwapExactTokensForTokensBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+        if let Some(observations) = self.observations.as_ref() {
+            let mut data = observations.as_ref().try_borrow_mut_data()?;
+            let (header, observations) = Observations::split_mut(&mut data);
+            header.write(
+                observations, 
+                self.pool.last_update_ts, 
+                self.pool.price_a_cumulative, 
+                self.pool.price_b_cumulative, 
+                self.mint_liquidity.supply + self.pool.minimum_liquidity
+            );
+        }
 
-        require!(output >= min_output_amount, ErrorCode::OutputTooSmall);
+        // 启用预言机保护时，成交价不能偏离参考价格太远
+        let (a_amount, b_amount) = if swap_a { (received_input, output) } else { (output, received_input) };
+        check_trade_price(&self.pool, self.price_feed.as_deref(), a_amount, b_amount)?;
 
-        // 计算交易前的不变量
-        let invariant = pool_a.amount * pool_b.amount;
+        let invariant = self.curve()?.invariant()?;
         
         let signer_seeds: &[&[&[u8]]] = &[&[
             &self.pool.amm.to_bytes(),
             &self.a_mint.key().to_bytes(),
             &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
             AUTHORITY_SEED.as_bytes(),
             &[bumps.pool_authority]
         ]];
@@ -72,7 +111,8 @@ impl<'info> SwapExactTokensForTokens<'info> {
                 self.token_program.to_account_info(), 
                 input, 
                 self.a_mint.decimals, 
-                None
+                None, 
+                remaining_accounts
             )?;
             
             transfer_token(
@@ -83,7 +123,8 @@ impl<'info> SwapExactTokensForTokens<'info> {
                 self.token_program.to_account_info(), 
                 output, 
                 self.b_mint.decimals, 
-                Some(signer_seeds)
+                Some(signer_seeds), 
+                remaining_accounts
             )?;
 
         } else {
@@ -95,7 +136,8 @@ impl<'info> SwapExactTokensForTokens<'info> {
                 self.token_program.to_account_info(), 
                 input, 
                 self.b_mint.decimals, 
-                None
+                None, 
+                remaining_accounts
             )?;
             
             transfer_token(
@@ -106,21 +148,33 @@ impl<'info> SwapExactTokensForTokens<'info> {
                 self.token_program.to_account_info(), 
                 output, 
                 self.a_mint.decimals, 
-                Some(signer_seeds)
+                Some(signer_seeds), 
+                remaining_accounts
             )?;
         }
 
-        msg!(
-            "Traded {} tokens ({} after fees) for {}",
-            input,
-            taxed_input,
-            output
-        );
+        // 更新内部储备量
+        let pool = &mut self.pool;
+        if swap_a {
+            pool.reserve_a = pool.reserve_a.checked_add(received_input).unwrap();
+            pool.reserve_b = pool.reserve_b.checked_sub(output).unwrap();
+        } else {
+            pool.reserve_a = pool.reserve_a.checked_sub(output).unwrap();
+            pool.reserve_b = pool.reserve_b.checked_add(received_input).unwrap();
+        }
+
+        let curve = self.curve()?;
+        require!(invariant <= curve.invariant()?, ErrorCode::InvariantViolated);
+        msg!("Spot price after swap: {}", curve.spot_price()?);
 
+        // 金库余额必须能覆盖内部储备量
         self.pool_account_a.reload()?;
         self.pool_account_b.reload()?;
 
-        require!(invariant <= self.pool_account_a.amount * self.pool_account_b.amount, ErrorCode::InvariantViolated);
+        require!(
+            self.pool_account_a.amount >= self.pool.reserve_a && self.pool_account_b.amount >= self.pool.reserve_b,
+            ErrorCode::InvariantViolated
+        );
 
         Ok(())
     }
@@ -142,15 +196,19 @@ pub struct SwapExactTokensForTokens<'info> {
     pub amm: Box<Account<'info, Amm>>,
 
     #[account(
+        mut,
         seeds = [
             amm.key().as_ref(),
             a_mint.key().as_ref(),
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
         ],
         bump,
         has_one = amm,
         has_one = a_mint,
-        has_one = b_mint
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency,
+        constraint = !pool.observations_enabled || observations.is_some() @ ErrorCode::MissingObservations
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -160,6 +218,7 @@ pub struct SwapExactTokensForTokens<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             AUTHORITY_SEED.as_bytes()
         ],
         bump
@@ -172,13 +231,29 @@ pub struct SwapExactTokensForTokens<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(),
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             LIQUIDITY_SEED.as_bytes()
         ],
         bump,
     )]
     pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
+    // 池子创建观测缓冲区后必须传入，由本次兑换写入一条价格观测
     #[account(
+        mut,
+        seeds = [
+            pool.key().as_ref(),
+            OBSERVATION_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub observations: Option<AccountLoader<'info, Observations>>,
+
+    // 池子启用预言机保护时必须传入
+    pub price_feed: Option<Box<Account<'info, PriceFeed>>>,
+
+    #[account(
+        mut,
         associated_token::mint = a_mint,
         associated_token::authority = pool_authority,
         associated_token::token_program = token_program
@@ -186,6 +261,7 @@ pub struct SwapExactTokensForTokens<'info> {
     pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
+        mut,
         associated_token::mint = b_mint,
         associated_token::authority = pool_authority,
         associated_token::token_program = token_program
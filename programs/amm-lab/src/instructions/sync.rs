@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::AUTHORITY_SEED, state::{Amm, Pool}};
+
+use super::shared::accumulate_price;
+
+
+impl<'info> Sync<'info> {
+    pub fn sync(&mut self) -> Result<()> {
+        // 储备量变化之前先按旧的储备量更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
+        // 以金库的实际余额作为新的储备量
+        let pool = &mut self.pool;
+        pool.reserve_a = self.pool_account_a.amount;
+        pool.reserve_b = self.pool_account_b.amount;
+
+        msg!("Synced reserves to {} and {}", pool.reserve_a, pool.reserve_b);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct Sync<'info> {
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        associated_token::mint = a_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        associated_token::mint = b_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>
+}
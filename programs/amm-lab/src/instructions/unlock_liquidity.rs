@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{constants::{LIQUIDITY_SEED, LOCK_SEED}, errors::ErrorCode, state::{LiquidityLock, Pool}};
+
+use super::shared::transfer_token;
+
+
+impl<'info> UnlockLiquidity<'info> {
+    pub fn unlock_liquidity(&mut self, bumps: &UnlockLiquidityBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let amount = self.lock.unlockable_amount(now);
+        require!(amount > 0, ErrorCode::NothingToUnlock);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.key().to_bytes(),
+            &self.owner.key().to_bytes(),
+            LOCK_SEED.as_bytes(),
+            &[bumps.lock]
+        ]];
+
+        transfer_token(
+            self.escrow_account_liquidity.to_account_info(), 
+            self.owner_account_liquidity.to_account_info(), 
+            self.mint_liquidity.to_account_info(), 
+            self.lock.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            amount, 
+            self.mint_liquidity.decimals, 
+            Some(signer_seeds), 
+            &[]
+        )?;
+
+        let lock = &mut self.lock;
+        lock.withdrawn = lock.withdrawn.checked_add(amount).unwrap();
+
+        msg!("Unlocked {} liquidity, {} still locked", amount, lock.amount - lock.withdrawn);
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            pool.a_mint.as_ref(),
+            pool.b_mint.as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+            LOCK_SEED.as_bytes()
+        ],
+        bump,
+        has_one = pool,
+        has_one = owner
+    )]
+    pub lock: Box<Account<'info, LiquidityLock>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = owner,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub owner_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = lock,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub escrow_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
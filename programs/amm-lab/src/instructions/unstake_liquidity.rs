@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{Mint, TokenAccount}
+};
+
+use crate::{constants::{FARM_SEED, STAKE_SEED}, errors::ErrorCode, state::{Farm, UserStake}};
+
+use super::shared::transfer_token;
+
+
+impl<'info> UnstakeLiquidity<'info> {
+    pub fn unstake_liquidity(&mut self, amount: u64, bumps: &UnstakeLiquidityBumps) -> Result<()> {
+        require!(amount > 0 && amount <= self.stake.amount, ErrorCode::InsufficientStake);
+
+        let farm = &mut self.farm;
+        farm.update_rewards(Clock::get()?.unix_timestamp);
+
+        // 先结算奖励再减少质押，已产生的奖励保留在待领取中
+        let stake = &mut self.stake;
+        stake.settle(farm);
+        stake.amount -= amount;
+        stake.reset_debts(farm);
+
+        farm.total_staked -= amount;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.farm.pool.to_bytes(),
+            FARM_SEED.as_bytes(),
+            &[bumps.farm]
+        ]];
+
+        transfer_token(
+            self.farm_account_liquidity.to_account_info(), 
+            self.owner_account_liquidity.to_account_info(), 
+            self.mint_liquidity.to_account_info(), 
+            self.farm.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            amount, 
+            self.mint_liquidity.decimals, 
+            Some(signer_seeds), 
+            &[]
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct UnstakeLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            farm.pool.as_ref(),
+            FARM_SEED.as_bytes()
+        ],
+        bump,
+        has_one = mint_liquidity
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            farm.key().as_ref(),
+            owner.key().as_ref(),
+            STAKE_SEED.as_bytes()
+        ],
+        bump,
+        has_one = farm,
+        has_one = owner
+    )]
+    pub stake: Box<Account<'info, UserStake>>,
+
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = owner,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub owner_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = farm,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub farm_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
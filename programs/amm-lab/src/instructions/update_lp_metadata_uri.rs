@@ -0,0 +1,120 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    token_2022::Token2022,
+    token_interface::{
+        spl_token_metadata_interface::state::Field,
+        token_metadata_update_field, Mint, TokenMetadataUpdateField
+    }
+};
+
+use crate::{
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MAX_URI_LEN}, 
+    errors::ErrorCode, 
+    state::{Amm, Pool}
+};
+
+
+impl<'info> UpdateLpMetadataUri<'info> {
+    pub fn update_lp_metadata_uri(&mut self, uri: String, bumps: &UpdateLpMetadataUriBumps) -> Result<()> {
+        require!(uri.len() <= MAX_URI_LEN, ErrorCode::InvalidMetadata);
+
+        // 新的 URI 可能使元数据变长，按最大增量补足租金
+        let mint_info = self.mint_liquidity.to_account_info();
+        let space = mint_info.data_len() + uri.len();
+        let lamports = Rent::get()?.minimum_balance(space).saturating_sub(mint_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(), 
+                    Transfer { 
+                        from: self.admin.to_account_info(), 
+                        to: mint_info.clone() 
+                    }
+                ), 
+                lamports
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.amm.key().to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                self.liquidity_token_program.to_account_info(), 
+                TokenMetadataUpdateField { 
+                    program_id: self.liquidity_token_program.to_account_info(), 
+                    metadata: mint_info, 
+                    update_authority: self.pool_authority.to_account_info() 
+                }, 
+                signer_seeds
+            ), 
+            Field::Uri, 
+            uri
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct UpdateLpMetadataUri<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump,
+        has_one = admin
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
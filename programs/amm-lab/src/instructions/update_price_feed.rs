@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::PRICE_FEED_SEED, state::PriceFeed};
+
+
+impl<'info> UpdatePriceFeed<'info> {
+    pub fn update_price_feed(&mut self, price: u128) -> Result<()> {
+        let price_feed = &mut self.price_feed;
+        price_feed.price = price;
+        price_feed.last_update_ts = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            price_feed.id.as_ref(),
+            PRICE_FEED_SEED.as_bytes()
+        ],
+        bump,
+        has_one = authority
+    )]
+    pub price_feed: Box<Account<'info, PriceFeed>>
+}
@@ -1,28 +1,70 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface}};
-use fixed::types::I64F64;
+use anchor_spl::{associated_token::AssociatedToken, token_2022::Token2022, token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface}};
 
-use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MINIMUM_LIQUIDITY}, state::{Amm, Pool}};
+use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, curve::{pool_curve, withdraw_amount}, errors::ErrorCode, state::{Amm, Pool}};
 
-use super::shared::transfer_token;
+use super::shared::{accumulate_price, amount_after_transfer_fee, mint_protocol_fee, transfer_token};
 
 
 impl<'info> WithdrawLiquidity<'info> {
-    pub fn withdraw_liquidity(&mut self, amount: u64, bumps: &WithdrawLiquidityBumps) -> Result<()> {
+    pub fn withdraw_liquidity(
+        &mut self, 
+        amount: u64, 
+        min_amount_a: u64, 
+        min_amount_b: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &WithdrawLiquidityBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格，紧急状态下曲线不可信，不再更新
+        if !self.pool.emergency {
+            accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+        }
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             &self.pool.amm.to_bytes(),
             &self.a_mint.key().to_bytes(),
             &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
             AUTHORITY_SEED.as_bytes(),
             &[bumps.pool_authority]
         ]];
 
-        let amount_a = I64F64::from_num(amount)
-            .checked_mul(I64F64::from_num(self.pool_account_a.amount))
-            .unwrap()
-            .checked_div(I64F64::from_num(self.mint_liquidity.supply + MINIMUM_LIQUIDITY))
-            .unwrap().floor()
-            .to_num::<u64>();
+        // 先按 sqrt(k) 的增长向国库铸造协议费，再按铸造后的总份额计算赎回数量，紧急状态下不收取
+        let fee_liquidity = if self.pool.emergency {
+            0
+        } else {
+            mint_protocol_fee(
+                &self.pool, 
+                &self.amm, 
+                &self.mint_liquidity, 
+                self.treasury_account_liquidity.as_ref().map(|account| account.to_account_info()), 
+                self.pool_authority.to_account_info(), 
+                self.liquidity_token_program.to_account_info(), 
+                signer_seeds
+            )?
+        };
+        let total_supply = self.mint_liquidity.supply + self.pool.minimum_liquidity + fee_liquidity;
+
+        let [amount_a, amount_b] = if self.pool.emergency {
+            // 紧急状态下绕过曲线，按金库实际余额等比例赎回
+            [self.pool_account_a.amount, self.pool_account_b.amount]
+                .map(|balance| withdraw_amount(amount, balance, total_supply))
+        } else {
+            let curve = pool_curve(
+                &self.pool, 
+                [self.pool.reserve_a, self.pool.reserve_b], 
+                self.amm.fee, 
+                [self.a_mint.decimals, self.b_mint.decimals]
+            )?;
+            curve.withdraw_amounts(amount, total_supply)
+        };
+
+        // 最低数量按用户扣除转账手续费后实际到账的数量检查
+        require!(
+            amount_after_transfer_fee(&self.a_mint.to_account_info(), amount_a)? >= min_amount_a 
+                && amount_after_transfer_fee(&self.b_mint.to_account_info(), amount_b)? >= min_amount_b, 
+            ErrorCode::OutputTooSmall
+        );
 
         transfer_token(
             self.pool_account_a.to_account_info(), 
@@ -32,17 +74,10 @@ impl<'info> WithdrawLiquidity<'info> {
             self.token_program.to_account_info(), 
             amount_a, 
             self.a_mint.decimals, 
-            Some(signer_seeds)
+            Some(signer_seeds), 
+            remaining_accounts
         )?;
 
-        let amount_b = I64F64::from_num(amount)
-            .checked_mul(I64F64::from_num(self.pool_account_b.amount))
-            .unwrap()
-            .checked_div(I64F64::from_num(self.mint_liquidity.supply + MINIMUM_LIQUIDITY))
-            .unwrap().floor()
-            .to_num::<u64>();
-
-
         transfer_token(
             self.pool_account_b.to_account_info(), 
             self.deposit_account_b.to_account_info(), 
@@ -51,12 +86,19 @@ impl<'info> WithdrawLiquidity<'info> {
             self.token_program.to_account_info(), 
             amount_b, 
             self.b_mint.decimals, 
-            Some(signer_seeds)
+            Some(signer_seeds), 
+            remaining_accounts
         )?;
 
+        // 紧急赎回按金库余额计算，可能超出内部储备量
+        let pool = &mut self.pool;
+        pool.reserve_a = pool.reserve_a.saturating_sub(amount_a);
+        pool.reserve_b = pool.reserve_b.saturating_sub(amount_b);
+        pool.update_k_last(self.amm.protocol_fee_on);
+
         burn(
             CpiContext::new(
-                self.token_program.to_account_info(), 
+                self.liquidity_token_program.to_account_info(), 
                 Burn { 
                             mint: self.mint_liquidity.to_account_info(), 
                             from: self.deposit_account_liquidity.to_account_info(), 
@@ -86,10 +128,12 @@ pub struct WithdrawLiquidity<'info> {
     pub amm: Box<Account<'info, Amm>>,
 
     #[account(
+        mut,
         seeds = [
             amm.key().as_ref(),
             a_mint.key().as_ref(),
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
         ],
         bump,
         has_one = a_mint,
@@ -103,6 +147,7 @@ pub struct WithdrawLiquidity<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(), 
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             AUTHORITY_SEED.as_bytes()
         ],
         bump
@@ -115,6 +160,7 @@ pub struct WithdrawLiquidity<'info> {
             amm.key().as_ref(),
             a_mint.key().as_ref(),
             b_mint.key().as_ref(),
+            pool.curve_type.seed(),
             LIQUIDITY_SEED.as_bytes()
         ],
         bump,
@@ -122,6 +168,7 @@ pub struct WithdrawLiquidity<'info> {
     pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
+        mut,
         associated_token::mint = a_mint,
         associated_token::authority = pool_authority,
         associated_token::token_program = token_program
@@ -129,6 +176,7 @@ pub struct WithdrawLiquidity<'info> {
     pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
+        mut,
         associated_token::mint = b_mint,
         associated_token::authority = pool_authority,
         associated_token::token_program = token_program
@@ -140,10 +188,18 @@ pub struct WithdrawLiquidity<'info> {
         payer = payer,
         associated_token::mint = mint_liquidity,
         associated_token::authority = deposit,
-        associated_token::token_program = token_program
+        associated_token::token_program = liquidity_token_program
     )]
     pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = amm.treasury,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub treasury_account_liquidity: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     #[account(
         init_if_needed,
         payer = payer,
@@ -164,5 +220,6 @@ pub struct WithdrawLiquidity<'info> {
 
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
 }
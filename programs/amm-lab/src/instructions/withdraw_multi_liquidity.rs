@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::Token2022, 
+    token_interface::{burn, Burn, Mint, TokenAccount}
+};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MULTI_POOL_SEED}, 
+    curve::withdraw_amount, 
+    errors::ErrorCode, 
+    state::MultiPool
+};
+
+use super::shared::{amount_after_transfer_fee, transfer_token};
+
+
+impl<'info> WithdrawMultiLiquidity<'info> {
+    // 剩余账户按成员顺序依次传入 [mint, 存款人代币账户, 池子金库, 代币程序]，之后是 transfer hook 需要的额外账户
+    pub fn withdraw_multi_liquidity(
+        &mut self, 
+        lp_amount: u64, 
+        min_amounts: Vec<u64>, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &WithdrawMultiLiquidityBumps
+    ) -> Result<()> {
+        let pool = &self.multi_pool;
+        let n = pool.tokens.len();
+        require!(
+            min_amounts.len() == n && remaining_accounts.len() >= n * 4, 
+            ErrorCode::InvalidTokenCount
+        );
+        let (token_accounts, extra_accounts) = remaining_accounts.split_at(n * 4);
+
+        // 按比例取回每个成员代币
+        let supply = self.mint_liquidity.supply;
+        let amounts = pool.tokens
+            .iter()
+            .map(|token| withdraw_amount(lp_amount, token.reserve, supply + pool.minimum_liquidity))
+            .collect::<Vec<_>>();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.multi_pool.amm.to_bytes(),
+            &self.multi_pool.id.to_bytes(),
+            MULTI_POOL_SEED.as_bytes(),
+            &[bumps.multi_pool]
+        ]];
+
+        for (k, accounts) in token_accounts.chunks(4).enumerate() {
+            let token = &self.multi_pool.tokens[k];
+            require_keys_eq!(accounts[0].key(), token.mint, ErrorCode::InvalidMint);
+            require_keys_eq!(accounts[2].key(), token.vault, ErrorCode::InvalidMint);
+            // 成员可以分别属于 SPL Token 与 Token-2022
+            require_keys_eq!(accounts[3].key(), *accounts[0].owner, ErrorCode::InvalidTokenProgram);
+
+            // 最低数量按用户扣除转账手续费后实际到账的数量检查
+            require!(
+                amount_after_transfer_fee(&accounts[0], amounts[k])? >= min_amounts[k], 
+                ErrorCode::OutputTooSmall
+            );
+
+            transfer_token(
+                accounts[2].clone(), 
+                accounts[1].clone(), 
+                accounts[0].clone(), 
+                self.multi_pool.to_account_info(), 
+                accounts[3].clone(), 
+                amounts[k], 
+                token.decimals, 
+                Some(signer_seeds), 
+                extra_accounts
+            )?;
+        }
+
+        for (token, amount) in self.multi_pool.tokens.iter_mut().zip(&amounts) {
+            token.reserve -= amount;
+        }
+
+        burn(
+            CpiContext::new(
+                self.liquidity_token_program.to_account_info(), 
+                Burn { 
+                    mint: self.mint_liquidity.to_account_info(), 
+                    from: self.deposit_account_liquidity.to_account_info(), 
+                    authority: self.deposit.to_account_info() 
+                }
+            ), 
+            lp_amount
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct WithdrawMultiLiquidity<'info> {
+    pub deposit: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub liquidity_token_program: Program<'info, Token2022>
+}
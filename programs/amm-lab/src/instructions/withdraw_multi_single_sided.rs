@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken, 
+    token_2022::Token2022, 
+    token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface}
+};
+
+use crate::{
+    constants::{LIQUIDITY_SEED, MULTI_POOL_SEED}, 
+    errors::ErrorCode, 
+    state::{Amm, MultiPool}
+};
+
+use super::shared::{amount_after_transfer_fee, multi_pool_withdraw_single, transfer_token};
+
+
+impl<'info> WithdrawMultiSingleSided<'info> {
+    pub fn withdraw_multi_single_sided(
+        &mut self, 
+        lp_amount: u64, 
+        min_out: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &WithdrawMultiSingleSidedBumps
+    ) -> Result<()> {
+        let pool = &self.multi_pool;
+        let i = pool.token_index(&self.mint.key())?;
+
+        // 赎回的流动性全部换成一个成员代币，锁定的最低流动性也计入总份额
+        let output = multi_pool_withdraw_single(
+            pool, 
+            lp_amount, 
+            self.amm.fee, 
+            i, 
+            self.mint_liquidity.supply + pool.minimum_liquidity
+        )?;
+        require!(
+            amount_after_transfer_fee(&self.mint.to_account_info(), output)? >= min_out, 
+            ErrorCode::OutputTooSmall
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.multi_pool.amm.to_bytes(),
+            &self.multi_pool.id.to_bytes(),
+            MULTI_POOL_SEED.as_bytes(),
+            &[bumps.multi_pool]
+        ]];
+
+        transfer_token(
+            self.pool_account.to_account_info(), 
+            self.deposit_account.to_account_info(), 
+            self.mint.to_account_info(), 
+            self.multi_pool.to_account_info(), 
+            self.token_program.to_account_info(), 
+            output, 
+            self.mint.decimals, 
+            Some(signer_seeds), 
+            remaining_accounts
+        )?;
+
+        let token = &mut self.multi_pool.tokens[i];
+        token.reserve = token.reserve.checked_sub(output).ok_or(error!(ErrorCode::MathOverflow))?;
+
+        msg!("Withdrew {} tokens for {} liquidity", output, lp_amount);
+
+        burn(
+            CpiContext::new(
+                self.liquidity_token_program.to_account_info(), 
+                Burn { 
+                    mint: self.mint_liquidity.to_account_info(), 
+                    from: self.deposit_account_liquidity.to_account_info(), 
+                    authority: self.deposit.to_account_info() 
+                }
+            ), 
+            lp_amount
+        )
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct WithdrawMultiSingleSided<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.amm.as_ref(),
+            multi_pool.id.as_ref(),
+            MULTI_POOL_SEED.as_bytes()
+        ],
+        bump,
+        has_one = amm
+    )]
+    pub multi_pool: Box<Account<'info, MultiPool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            multi_pool.key().as_ref(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = multi_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_2022::Token2022, token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface}};
+
+use crate::{constants::{AUTHORITY_SEED, LIQUIDITY_SEED}, curve::pool_curve, errors::ErrorCode, state::{Amm, Pool, PriceFeed}};
+
+use super::shared::{accumulate_price, amount_after_transfer_fee, check_trade_price, mint_protocol_fee, transfer_token};
+
+
+impl<'info> WithdrawSingleSided<'info> {
+    pub fn withdraw_single_sided(
+        &mut self, 
+        lp_amount: u64, 
+        out_token: Pubkey, 
+        min_out: u64, 
+        remaining_accounts: &[AccountInfo<'info>], 
+        bumps: &WithdrawSingleSidedBumps
+    ) -> Result<()> {
+        // 储备量变化之前先更新累计价格
+        accumulate_price(&mut self.pool, self.amm.fee, [self.a_mint.decimals, self.b_mint.decimals])?;
+
+        require!(
+            out_token == self.a_mint.key() || out_token == self.b_mint.key(), 
+            ErrorCode::InvalidMint
+        );
+        let out_a = out_token == self.a_mint.key();
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            &self.pool.amm.to_bytes(),
+            &self.a_mint.key().to_bytes(),
+            &self.b_mint.key().to_bytes(),
+            self.pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes(),
+            &[bumps.pool_authority]
+        ]];
+
+        // 先按 sqrt(k) 的增长向国库铸造协议费，再按铸造后的总份额计算赎回数量
+        let fee_liquidity = mint_protocol_fee(
+            &self.pool, 
+            &self.amm, 
+            &self.mint_liquidity, 
+            self.treasury_account_liquidity.as_ref().map(|account| account.to_account_info()), 
+            self.pool_authority.to_account_info(), 
+            self.liquidity_token_program.to_account_info(), 
+            signer_seeds
+        )?;
+        let total_supply = self.mint_liquidity.supply + self.pool.minimum_liquidity + fee_liquidity;
+
+        // 先按比例计算两侧可取回的数量
+        let decimals = [self.a_mint.decimals, self.b_mint.decimals];
+        let curve = pool_curve(&self.pool, [self.pool.reserve_a, self.pool.reserve_b], self.amm.fee, decimals)?;
+        let [amount_a, amount_b] = curve.withdraw_amounts(
+            lp_amount, 
+            total_supply
+        );
+
+        // 再将不需要的一侧按正常费率在池内兑换成目标代币
+        let reserves = [self.pool.reserve_a - amount_a, self.pool.reserve_b - amount_b];
+        let curve = pool_curve(&self.pool, reserves, self.amm.fee, decimals)?;
+        let (taxed_input, output) = if out_a {
+            curve.swap_out(amount_b, false)?
+        } else {
+            curve.swap_out(amount_a, true)?
+        };
+
+        // 启用预言机保护时，内部兑换的成交价不能偏离参考价格太远
+        let (a_amount, b_amount) = if out_a { (output, amount_b) } else { (amount_a, output) };
+        check_trade_price(&self.pool, self.price_feed.as_deref(), a_amount, b_amount)?;
+
+        let total_output = if out_a {
+            amount_a + output
+        } else {
+            amount_b + output
+        };
+        // 被兑换的一侧没有离开池子，只有最终转出时扣除转账手续费
+        let out_mint = if out_a { self.a_mint.to_account_info() } else { self.b_mint.to_account_info() };
+        require!(amount_after_transfer_fee(&out_mint, total_output)? >= min_out, ErrorCode::OutputTooSmall);
+
+        if out_a {
+            transfer_token(
+                self.pool_account_a.to_account_info(), 
+                self.deposit_account_a.to_account_info(), 
+                self.a_mint.to_account_info(), 
+                self.pool_authority.to_account_info(), 
+                self.token_program.to_account_info(), 
+                total_output, 
+                self.a_mint.decimals, 
+                Some(signer_seeds), 
+                remaining_accounts
+            )?;
+        } else {
+            transfer_token(
+                self.pool_account_b.to_account_info(), 
+                self.deposit_account_b.to_account_info(), 
+                self.b_mint.to_account_info(), 
+                self.pool_authority.to_account_info(), 
+                self.token_program.to_account_info(), 
+                total_output, 
+                self.b_mint.decimals, 
+                Some(signer_seeds), 
+                remaining_accounts
+            )?;
+        }
+
+        // 被兑换的一侧又回到了池子里，因此只有输出侧的储备量减少
+        let pool = &mut self.pool;
+        if out_a {
+            pool.reserve_a = pool.reserve_a.checked_sub(total_output).unwrap();
+        } else {
+            pool.reserve_b = pool.reserve_b.checked_sub(total_output).unwrap();
+        }
+        pool.update_k_last(self.amm.protocol_fee_on);
+
+        msg!(
+            "Withdrew {} tokens ({} swapped, {} after fees) for {} liquidity",
+            total_output,
+            if out_a { amount_b } else { amount_a },
+            taxed_input,
+            lp_amount
+        );
+
+        burn(
+            CpiContext::new(
+                self.liquidity_token_program.to_account_info(), 
+                Burn { 
+                            mint: self.mint_liquidity.to_account_info(), 
+                            from: self.deposit_account_liquidity.to_account_info(), 
+                            authority: self.deposit.to_account_info() 
+                        }
+                ), 
+            lp_amount
+        )?;
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct WithdrawSingleSided<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub deposit: Signer<'info>,
+    pub a_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [amm.id.as_ref()],
+        bump
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = a_mint,
+        has_one = b_mint,
+        constraint = !pool.emergency @ ErrorCode::PoolInEmergency
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(), 
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            AUTHORITY_SEED.as_bytes()
+        ],
+        bump
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            a_mint.key().as_ref(),
+            b_mint.key().as_ref(),
+            pool.curve_type.seed(),
+            LIQUIDITY_SEED.as_bytes()
+        ],
+        bump,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = a_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = b_mint,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = deposit,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub deposit_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = amm.treasury,
+        associated_token::token_program = liquidity_token_program
+    )]
+    pub treasury_account_liquidity: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // 池子启用预言机保护时必须传入
+    pub price_feed: Option<Box<Account<'info, PriceFeed>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = a_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = b_mint,
+        associated_token::authority = deposit,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub liquidity_token_program: Program<'info, Token2022>
+}
@@ -4,8 +4,11 @@ mod state;
 mod instructions;
 mod errors;
 mod constants;
+mod math;
+mod curve;
 
 pub use instructions::*;
+pub use state::{twap, CurveType, Observation, PoolCurve};
 
 declare_id!("BLWvcgaBfsQLkfxcxg4afZzfQWZZKD5L5QcJDb9n6ag3");
 
@@ -13,29 +16,293 @@ declare_id!("BLWvcgaBfsQLkfxcxg4afZzfQWZZKD5L5QcJDb9n6ag3");
 pub mod amm_lab {
     use super::*;
 
-    pub fn create_amm(ctx: Context<CreateAmm>, id: Pubkey, fee: u16) -> Result<()> {
-        ctx.accounts.create_amm(id, fee)
+    pub fn create_amm(
+        ctx: Context<CreateAmm>,
+        id: Pubkey,
+        fee: u16,
+        lp_decimals: u8,
+        minimum_liquidity: u64
+    ) -> Result<()> {
+        ctx.accounts.create_amm(id, fee, lp_decimals, minimum_liquidity)
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>, curve: PoolCurve) -> Result<()> {
+        ctx.accounts.create_pool(curve, &ctx.bumps)
+    }
+
+    pub fn create_pool_with_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePoolWithLiquidity<'info>>, 
+        curve: PoolCurve, 
+        amount_a: u64, 
+        amount_b: u64
+    ) -> Result<()> {
+        ctx.accounts.create_pool_with_liquidity(
+            curve, 
+            amount_a, 
+            amount_b, 
+            ctx.remaining_accounts, 
+            &ctx.bumps
+        )
+    }
+
+    pub fn set_lp_metadata_uri(ctx: Context<SetLpMetadataUri>, uri: String) -> Result<()> {
+        ctx.accounts.set_lp_metadata_uri(uri)
+    }
+
+    pub fn set_lp_config(ctx: Context<SetLpConfig>, lp_decimals: u8, minimum_liquidity: u64) -> Result<()> {
+        ctx.accounts.set_lp_config(lp_decimals, minimum_liquidity)
     }
 
-    pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
-        ctx.accounts.create_pool()
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, protocol_fee_on: bool, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.set_protocol_fee(protocol_fee_on, treasury)
     }
 
-    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount_a: u64, amount_b: u64) -> Result<()> {
-        ctx.accounts.deposit_liquidity(amount_a, amount_b, &ctx.bumps)
+    pub fn update_lp_metadata_uri(ctx: Context<UpdateLpMetadataUri>, uri: String) -> Result<()> {
+        ctx.accounts.update_lp_metadata_uri(uri, &ctx.bumps)
     }
 
-    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw_liquidity(amount, &ctx.bumps)
+    pub fn deposit_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositLiquidity<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity_out: u64
+    ) -> Result<()> {
+        ctx.accounts.deposit_liquidity(amount_a, amount_b, min_liquidity_out, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn deposit_single_sided<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositSingleSided<'info>>,
+        token: Pubkey,
+        amount: u64,
+        min_liquidity: u64
+    ) -> Result<()> {
+        ctx.accounts.deposit_single_sided(token, amount, min_liquidity, ctx.remaining_accounts, &ctx.bumps)
     }
 
-    pub fn swap_exact_tokens_for_tokens(
-        ctx: Context<SwapExactTokensForTokens>,
+    pub fn deposit_exact_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositExactLiquidity<'info>>,
+        lp_amount: u64,
+        max_a: u64,
+        max_b: u64
+    ) -> Result<()> {
+        ctx.accounts.deposit_exact_liquidity(lp_amount, max_a, max_b, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn withdraw_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawLiquidity<'info>>,
+        amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_liquidity(amount, min_amount_a, min_amount_b, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn withdraw_single_sided<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawSingleSided<'info>>,
+        lp_amount: u64,
+        out_token: Pubkey,
+        min_out: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_single_sided(lp_amount, out_token, min_out, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn swap_exact_tokens_for_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapExactTokensForTokens<'info>>,
         swap_a: bool,
         input_amount: u64,
         min_output_amount: u64
     ) -> Result<()> {
-        ctx.accounts.swap_exact_tokens_fro_tokens(swap_a, input_amount, min_output_amount, &ctx.bumps)
+        ctx.accounts.swap_exact_tokens_fro_tokens(swap_a, input_amount, min_output_amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn init_observations(ctx: Context<InitObservations>) -> Result<()> {
+        ctx.accounts.init_observations()
+    }
+
+    pub fn grow_observations(ctx: Context<GrowObservations>, capacity: u16) -> Result<()> {
+        ctx.accounts.grow_observations(capacity)
+    }
+
+    pub fn observe(ctx: Context<Observe>, seconds_ago: Vec<u32>) -> Result<Vec<Observation>> {
+        ctx.accounts.observe(seconds_ago)
+    }
+
+    pub fn get_pool_info(ctx: Context<GetPoolInfo>) -> Result<PoolInfo> {
+        ctx.accounts.get_pool_info()
+    }
+
+    pub fn quote_swap_in(ctx: Context<GetPoolInfo>, swap_a: bool, output: u64) -> Result<u64> {
+        ctx.accounts.quote_swap_in(swap_a, output)
+    }
+
+    pub fn lock_liquidity(ctx: Context<LockLiquidity>, amount: u64, unlock_ts: i64, linear: bool) -> Result<()> {
+        ctx.accounts.lock_liquidity(amount, unlock_ts, linear)
+    }
+
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        ctx.accounts.unlock_liquidity(&ctx.bumps)
+    }
+
+    pub fn create_farm(ctx: Context<CreateFarm>) -> Result<()> {
+        ctx.accounts.create_farm()
+    }
+
+    pub fn add_farm_reward(ctx: Context<AddFarmReward>, emission_per_second: u64) -> Result<()> {
+        ctx.accounts.add_farm_reward(emission_per_second)
+    }
+
+    pub fn set_farm_emission(ctx: Context<SetFarmEmission>, index: u8, emission_per_second: u64) -> Result<()> {
+        ctx.accounts.set_farm_emission(index, emission_per_second)
+    }
+
+    pub fn stake_liquidity(ctx: Context<StakeLiquidity>, amount: u64) -> Result<()> {
+        ctx.accounts.stake_liquidity(amount)
+    }
+
+    pub fn unstake_liquidity(ctx: Context<UnstakeLiquidity>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake_liquidity(amount, &ctx.bumps)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, index: u8) -> Result<()> {
+        ctx.accounts.claim_rewards(index, &ctx.bumps)
+    }
+
+    pub fn sync(ctx: Context<Sync>) -> Result<()> {
+        ctx.accounts.sync()
+    }
+
+    pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, Skim<'info>>) -> Result<()> {
+        ctx.accounts.skim(ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn create_cl_pool(ctx: Context<CreateClPool>, tick_spacing: u16, sqrt_price_x64: u128) -> Result<()> {
+        ctx.accounts.create_cl_pool(tick_spacing, sqrt_price_x64)
+    }
+
+    pub fn init_tick_array(ctx: Context<InitTickArray>, start_tick_index: i32) -> Result<()> {
+        ctx.accounts.init_tick_array(start_tick_index)
+    }
+
+    pub fn open_position(
+        ctx: Context<OpenPosition>, 
+        tick_lower: i32, 
+        tick_upper: i32
+    ) -> Result<()> {
+        ctx.accounts.open_position(tick_lower, tick_upper, &ctx.bumps)
+    }
+
+    pub fn increase_liquidity(
+        ctx: Context<IncreaseLiquidity>, 
+        liquidity: u128, 
+        max_amount_a: u64, 
+        max_amount_b: u64
+    ) -> Result<()> {
+        ctx.accounts.increase_liquidity(liquidity, max_amount_a, max_amount_b)
+    }
+
+    pub fn decrease_liquidity(
+        ctx: Context<DecreaseLiquidity>, 
+        liquidity: u128, 
+        min_amount_a: u64, 
+        min_amount_b: u64
+    ) -> Result<()> {
+        ctx.accounts.decrease_liquidity(liquidity, min_amount_a, min_amount_b, &ctx.bumps)
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        ctx.accounts.collect_fees(&ctx.bumps)
+    }
+
+    pub fn cl_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClSwap<'info>>, 
+        a_to_b: bool, 
+        input_amount: u64, 
+        min_output_amount: u64, 
+        sqrt_price_limit_x64: u128
+    ) -> Result<()> {
+        ctx.accounts.cl_swap(
+            a_to_b, 
+            input_amount, 
+            min_output_amount, 
+            sqrt_price_limit_x64, 
+            ctx.remaining_accounts, 
+            &ctx.bumps
+        )
+    }
+
+    pub fn ramp_amp(ctx: Context<RampAmp>, target_amp: u64, ramp_stop_ts: i64) -> Result<()> {
+        ctx.accounts.ramp_amp(target_amp, ramp_stop_ts)
+    }
+
+    pub fn stop_ramp_amp(ctx: Context<RampAmp>) -> Result<()> {
+        ctx.accounts.stop_ramp_amp()
+    }
+
+    pub fn set_sum_rate(ctx: Context<SetSumRate>, sum_rate: u64, ramp_stop_ts: i64) -> Result<()> {
+        ctx.accounts.set_sum_rate(sum_rate, ramp_stop_ts)
+    }
+
+    pub fn set_emergency(ctx: Context<SetEmergency>, emergency: bool) -> Result<()> {
+        ctx.accounts.set_emergency(emergency)
     }
-}
 
+    pub fn create_price_feed(ctx: Context<CreatePriceFeed>, id: Pubkey, price: u128) -> Result<()> {
+        ctx.accounts.create_price_feed(id, price)
+    }
+
+    pub fn update_price_feed(ctx: Context<UpdatePriceFeed>, price: u128) -> Result<()> {
+        ctx.accounts.update_price_feed(price)
+    }
+
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>, max_price_deviation: u16, max_price_age: i64) -> Result<()> {
+        ctx.accounts.set_price_feed(max_price_deviation, max_price_age)
+    }
+
+    pub fn create_multi_pool(ctx: Context<CreateMultiPool>, id: Pubkey, curve_type: CurveType, amp: u64) -> Result<()> {
+        ctx.accounts.create_multi_pool(id, curve_type, amp)
+    }
+
+    pub fn add_multi_pool_token(ctx: Context<AddMultiPoolToken>, weight: u64) -> Result<()> {
+        ctx.accounts.add_multi_pool_token(weight)
+    }
+
+    pub fn deposit_multi_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositMultiLiquidity<'info>>, 
+        lp_amount: u64, 
+        max_amounts: Vec<u64>
+    ) -> Result<()> {
+        ctx.accounts.deposit_multi_liquidity(lp_amount, max_amounts, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn deposit_multi_single_sided<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositMultiSingleSided<'info>>, 
+        amount: u64, 
+        min_liquidity: u64
+    ) -> Result<()> {
+        ctx.accounts.deposit_multi_single_sided(amount, min_liquidity, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn withdraw_multi_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawMultiLiquidity<'info>>, 
+        lp_amount: u64, 
+        min_amounts: Vec<u64>
+    ) -> Result<()> {
+        ctx.accounts.withdraw_multi_liquidity(lp_amount, min_amounts, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn withdraw_multi_single_sided<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawMultiSingleSided<'info>>, 
+        lp_amount: u64, 
+        min_out: u64
+    ) -> Result<()> {
+        ctx.accounts.withdraw_multi_single_sided(lp_amount, min_out, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn multi_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, MultiSwap<'info>>, 
+        input_amount: u64, 
+        min_output_amount: u64
+    ) -> Result<()> {
+        ctx.accounts.multi_swap(input_amount, min_output_amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+}
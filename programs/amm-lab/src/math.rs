@@ -0,0 +1,7 @@
+pub mod big_num;
+pub mod tick_math;
+pub mod sqrt_price_math;
+pub mod swap_math;
+pub mod liquidity_math;
+pub mod stable_math;
+pub mod weighted_math;
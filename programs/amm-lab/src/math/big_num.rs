@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub use u256::U256;
+
+
+#[allow(clippy::assign_op_pattern, clippy::ptr_offset_with_cast, clippy::manual_range_contains, clippy::manual_div_ceil)]
+mod u256 {
+    use uint::construct_uint;
+
+    construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+
+pub fn to_u128(value: U256) -> Result<u128> {
+    require!(value.bits() <= 128, ErrorCode::MathOverflow);
+    Ok(value.as_u128())
+}
+
+
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| error!(ErrorCode::MathOverflow))
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+
+pub fn add_delta(liquidity: u128, delta: i128) -> Result<u128> {
+    let result = if delta < 0 {
+        liquidity.checked_sub(delta.unsigned_abs())
+    } else {
+        liquidity.checked_add(delta as u128)
+    };
+
+    result.ok_or(error!(ErrorCode::MathOverflow))
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::big_num::{to_u128, U256};
+
+
+// 价格区间内 a 的数量：Δa = L * (√Pu - √Pl) / (√Pu * √Pl)
+pub fn get_amount_a_delta(
+    sqrt_price_a_x64: u128, 
+    sqrt_price_b_x64: u128, 
+    liquidity: u128, 
+    round_up: bool
+) -> Result<u128> {
+    let (lower, upper) = if sqrt_price_a_x64 > sqrt_price_b_x64 {
+        (sqrt_price_b_x64, sqrt_price_a_x64)
+    } else {
+        (sqrt_price_a_x64, sqrt_price_b_x64)
+    };
+    require!(lower > 0, ErrorCode::InvalidSqrtPrice);
+
+    let product = U256::from(liquidity) * U256::from(upper - lower);
+    require!(product.leading_zeros() >= 64, ErrorCode::MathOverflow);
+
+    let numerator = product << 64;
+    let denominator = U256::from(lower) * U256::from(upper);
+    let (quotient, remainder) = numerator.div_mod(denominator);
+
+    if round_up && !remainder.is_zero() {
+        to_u128(quotient + 1)
+    } else {
+        to_u128(quotient)
+    }
+}
+
+
+// 价格区间内 b 的数量：Δb = L * (√Pu - √Pl)
+pub fn get_amount_b_delta(
+    sqrt_price_a_x64: u128, 
+    sqrt_price_b_x64: u128, 
+    liquidity: u128, 
+    round_up: bool
+) -> Result<u128> {
+    let diff = sqrt_price_a_x64.abs_diff(sqrt_price_b_x64);
+
+    let product = U256::from(liquidity) * U256::from(diff);
+    let quotient = product >> 64;
+
+    if round_up && product.low_u64() != 0 {
+        to_u128(quotient + 1)
+    } else {
+        to_u128(quotient)
+    }
+}
+
+
+// 加入 amount 个 a 后的价格，向上取整使价格下降得更少
+fn get_next_sqrt_price_from_amount_a_round_up(
+    sqrt_price_x64: u128, 
+    liquidity: u128, 
+    amount: u64
+) -> Result<u128> {
+    if amount == 0 {
+        return Ok(sqrt_price_x64);
+    }
+
+    // √P' = L * √P / (L + Δa * √P)
+    let numerator = U256::from(liquidity) << 64;
+    let product = U256::from(amount) * U256::from(sqrt_price_x64);
+    let denominator = numerator + product;
+
+    match numerator.checked_mul(U256::from(sqrt_price_x64)) {
+        Some(full) => {
+            let (quotient, remainder) = full.div_mod(denominator);
+            if remainder.is_zero() {
+                to_u128(quotient)
+            } else {
+                to_u128(quotient + 1)
+            }
+        },
+        None => {
+            // 乘积溢出时改用 L / (L / √P + Δa)
+            let divisor = numerator / U256::from(sqrt_price_x64) + U256::from(amount);
+            let (quotient, remainder) = numerator.div_mod(divisor);
+            if remainder.is_zero() {
+                to_u128(quotient)
+            } else {
+                to_u128(quotient + 1)
+            }
+        }
+    }
+}
+
+
+// 加入 amount 个 b 后的价格，向下取整使价格上升得更少
+fn get_next_sqrt_price_from_amount_b_round_down(
+    sqrt_price_x64: u128, 
+    liquidity: u128, 
+    amount: u64
+) -> Result<u128> {
+    // √P' = √P + Δb / L
+    let quotient = (U256::from(amount) << 64) / U256::from(liquidity);
+
+    to_u128(U256::from(sqrt_price_x64) + quotient)
+}
+
+
+pub fn get_next_sqrt_price_from_input(
+    sqrt_price_x64: u128, 
+    liquidity: u128, 
+    amount_in: u64, 
+    a_to_b: bool
+) -> Result<u128> {
+    require!(sqrt_price_x64 > 0 && liquidity > 0, ErrorCode::InvalidSqrtPrice);
+
+    if a_to_b {
+        get_next_sqrt_price_from_amount_a_round_up(sqrt_price_x64, liquidity, amount_in)
+    } else {
+        get_next_sqrt_price_from_amount_b_round_down(sqrt_price_x64, liquidity, amount_in)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u128 = 1 << 64;
+
+    #[test]
+    fn amount_deltas_between_one_and_four() {
+        // √P 从 1 到 2：Δa = L * (2 - 1) / 2，Δb = L * (2 - 1)
+        let (lower, upper) = (ONE, 2 * ONE);
+        assert_eq!(get_amount_a_delta(lower, upper, 1_000, false).unwrap(), 500);
+        assert_eq!(get_amount_a_delta(upper, lower, 1_000, true).unwrap(), 500);
+        assert_eq!(get_amount_b_delta(lower, upper, 1_000, false).unwrap(), 1_000);
+        assert_eq!(get_amount_b_delta(upper, lower, 1_000, true).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn amount_deltas_round_in_the_requested_direction() {
+        let (lower, upper) = (ONE, 2 * ONE);
+        assert_eq!(get_amount_a_delta(lower, upper, 1_001, false).unwrap(), 500);
+        assert_eq!(get_amount_a_delta(lower, upper, 1_001, true).unwrap(), 501);
+
+        let upper = ONE + ONE / 3;
+        assert_eq!(get_amount_b_delta(lower, upper, 1_000, false).unwrap(), 333);
+        assert_eq!(get_amount_b_delta(lower, upper, 1_000, true).unwrap(), 334);
+    }
+
+    #[test]
+    fn next_sqrt_price_moves_in_swap_direction() {
+        let liquidity = 1_000_000_000u128;
+        assert_eq!(get_next_sqrt_price_from_input(ONE, liquidity, 0, true).unwrap(), ONE);
+        assert!(get_next_sqrt_price_from_input(ONE, liquidity, 1_000, true).unwrap() < ONE);
+        assert!(get_next_sqrt_price_from_input(ONE, liquidity, 1_000, false).unwrap() > ONE);
+        assert!(get_next_sqrt_price_from_input(ONE, 0, 1_000, true).is_err());
+    }
+
+    #[test]
+    fn next_sqrt_price_never_requires_more_than_input() {
+        let liquidity = 123_456_789u128;
+        for amount in [1u64, 17, 1_000, 999_999, 50_000_000] {
+            let sqrt_price = get_next_sqrt_price_from_input(ONE, liquidity, amount, true).unwrap();
+            assert!(get_amount_a_delta(sqrt_price, ONE, liquidity, true).unwrap() <= amount as u128);
+
+            let sqrt_price = get_next_sqrt_price_from_input(ONE, liquidity, amount, false).unwrap();
+            assert!(get_amount_b_delta(ONE, sqrt_price, liquidity, true).unwrap() <= amount as u128);
+        }
+    }
+}
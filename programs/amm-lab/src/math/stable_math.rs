@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::big_num::{to_u128, U256};
+
+
+// Newton 迭代的最大次数，正常情况下几次就会收敛
+const MAX_ITERATIONS: usize = 255;
+
+
+// 求解 StableSwap 不变量 D：
+// A * n^n * sum(x) + D = A * n^n * D + D^(n+1) / (n^n * prod(x))
+// balances 需要事先统一到相同精度
+pub fn compute_d(amp: u64, balances: &[u128]) -> Result<u128> {
+    let n = U256::from(balances.len());
+    let sum = balances.iter().fold(U256::zero(), |acc, &x| acc + U256::from(x));
+    if sum.is_zero() {
+        return Ok(0);
+    }
+
+    let ann = U256::from(amp) * n.pow(n);
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let mut d_p = d;
+        for &x in balances {
+            require!(x > 0, ErrorCode::EmptyPool);
+            d_p = d_p * d / (U256::from(x) * n);
+        }
+
+        let d_prev = d;
+        d = (ann * sum + d_p * n) * d 
+            / ((ann - U256::one()) * d + (n + U256::one()) * d_p);
+
+        if d.abs_diff(d_prev) <= U256::one() {
+            return to_u128(d);
+        }
+    }
+
+    err!(ErrorCode::MathOverflow)
+}
+
+
+// 将第 i 个代币的余额改为 x 后，求保持 D 不变时第 j 个代币的余额
+pub fn compute_y(amp: u64, balances: &[u128], i: usize, j: usize, x: u128) -> Result<u128> {
+    require!(i != j && i < balances.len() && j < balances.len(), ErrorCode::InvalidMint);
+
+    let d = compute_d(amp, balances)?;
+    let mut balances = balances.to_vec();
+    balances[i] = x;
+
+    compute_y_d(amp, &balances, j, d)
+}
+
+
+// 其余代币余额不变时，求使不变量等于 d 的第 i 个代币余额，balances[i] 本身不参与计算
+pub fn compute_y_d(amp: u64, balances: &[u128], i: usize, d: u128) -> Result<u128> {
+    require!(i < balances.len(), ErrorCode::InvalidMint);
+
+    let d = U256::from(d);
+    let n = U256::from(balances.len());
+    let ann = U256::from(amp) * n.pow(n);
+
+    let mut c = d;
+    let mut sum = U256::zero();
+    for (k, &balance) in balances.iter().enumerate() {
+        if k == i {
+            continue;
+        }
+        require!(balance > 0, ErrorCode::EmptyPool);
+
+        sum += U256::from(balance);
+        c = c * d / (U256::from(balance) * n);
+    }
+    c = c * d / (ann * n);
+    let b = sum + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        y = (y * y + c) / (U256::from(2) * y + b - d);
+
+        if y.abs_diff(y_prev) <= U256::one() {
+            return to_u128(y);
+        }
+    }
+
+    err!(ErrorCode::MathOverflow)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按不变量公式直接验算 D，返回两边之差相对于 D 的比例
+    fn invariant_error(amp: u64, balances: &[u128], d: u128) -> f64 {
+        let n = balances.len() as f64;
+        let d = d as f64;
+        let sum: f64 = balances.iter().map(|&x| x as f64).sum();
+        let prod: f64 = balances.iter().map(|&x| x as f64).product();
+        let ann = amp as f64 * n.powf(n);
+        let left = ann * sum + d;
+        let right = ann * d + d.powf(n + 1.0) / (n.powf(n) * prod);
+
+        (left - right).abs() / right
+    }
+
+    #[test]
+    fn d_equals_sum_for_balanced_pool() {
+        assert_eq!(compute_d(100, &[0, 0]).unwrap(), 0);
+        assert_eq!(compute_d(100, &[1_000_000, 1_000_000]).unwrap(), 2_000_000);
+        assert_eq!(compute_d(1, &[5_000, 5_000, 5_000]).unwrap(), 15_000);
+    }
+
+    #[test]
+    fn d_converges_for_imbalanced_pools() {
+        let cases: [(u64, &[u128]); 4] = [
+            (1, &[1_000_000_000, 10_000_000]),
+            (100, &[1_000_000_000_000_000_000, 3_000_000_000_000_000]),
+            (2_000, &[1_000_000, 999_000_000]),
+            (100, &[7_000_000, 2_000_000, 11_000_000])
+        ];
+        for (amp, balances) in cases {
+            let d = compute_d(amp, balances).unwrap();
+            let sum: u128 = balances.iter().sum();
+            assert!(d <= sum);
+            assert!(invariant_error(amp, balances, d) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn y_keeps_d_unchanged() {
+        let balances = [1_000_000_000_000u128, 800_000_000_000];
+        for amp in [1, 10, 100, 5_000] {
+            let d = compute_d(amp, &balances).unwrap();
+            let x = balances[0] + 50_000_000_000;
+            let y = compute_y(amp, &balances, 0, 1, x).unwrap();
+            assert!(y < balances[1]);
+
+            let d_after = compute_d(amp, &[x, y]).unwrap();
+            assert!(d_after.abs_diff(d) <= 2);
+        }
+    }
+
+    #[test]
+    fn high_amp_trades_close_to_one_to_one() {
+        let balances = [1_000_000_000u128, 1_000_000_000];
+        let y = compute_y(5_000, &balances, 0, 1, 1_001_000_000).unwrap();
+        let output = balances[1] - y;
+        assert!(output <= 1_000_000 && output > 999_000);
+    }
+
+    #[test]
+    fn y_rejects_invalid_indices() {
+        let balances = [1_000u128, 1_000];
+        assert!(compute_y(100, &balances, 0, 0, 1_100).is_err());
+        assert!(compute_y(100, &balances, 0, 2, 1_100).is_err());
+        assert!(compute_y_d(100, &balances, 2, 2_000).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use super::{
+    big_num::to_u64,
+    sqrt_price_math::{get_amount_a_delta, get_amount_b_delta, get_next_sqrt_price_from_input}
+};
+
+
+pub const FEE_DENOMINATOR: u64 = 10000;
+
+
+pub struct SwapStep {
+    pub sqrt_price_next_x64: u128,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64
+}
+
+
+// 在单个 tick 区间内按精确输入兑换，价格最多移动到 sqrt_price_target_x64
+pub fn compute_swap_step(
+    sqrt_price_current_x64: u128, 
+    sqrt_price_target_x64: u128, 
+    liquidity: u128, 
+    amount_remaining: u64, 
+    fee: u16
+) -> Result<SwapStep> {
+    let a_to_b = sqrt_price_current_x64 >= sqrt_price_target_x64;
+
+    let amount_remaining_less_fee = (amount_remaining as u128 
+        * (FEE_DENOMINATOR - fee as u64) as u128 
+        / FEE_DENOMINATOR as u128) as u64;
+
+    let amount_in_to_target = if a_to_b {
+        get_amount_a_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, true)?
+    } else {
+        get_amount_b_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, true)?
+    };
+
+    let sqrt_price_next_x64 = if amount_remaining_less_fee as u128 >= amount_in_to_target {
+        sqrt_price_target_x64
+    } else {
+        get_next_sqrt_price_from_input(
+            sqrt_price_current_x64, 
+            liquidity, 
+            amount_remaining_less_fee, 
+            a_to_b
+        )?
+    };
+    let reached_target = sqrt_price_next_x64 == sqrt_price_target_x64;
+
+    let (amount_in, amount_out) = if a_to_b {
+        (
+            if reached_target {
+                amount_in_to_target
+            } else {
+                get_amount_a_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, true)?
+            },
+            get_amount_b_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, false)?
+        )
+    } else {
+        (
+            if reached_target {
+                amount_in_to_target
+            } else {
+                get_amount_b_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, true)?
+            },
+            get_amount_a_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, false)?
+        )
+    };
+    let amount_in = to_u64(amount_in)?;
+    let amount_out = to_u64(amount_out)?;
+
+    // 未到达目标价格时剩余输入全部计为手续费
+    let fee_amount = if reached_target {
+        to_u64(
+            (amount_in as u128 * fee as u128).div_ceil((FEE_DENOMINATOR - fee as u64) as u128)
+        )?
+    } else {
+        amount_remaining - amount_in
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next_x64,
+        amount_in,
+        amount_out,
+        fee_amount
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tick_math::get_sqrt_price_at_tick;
+
+    #[test]
+    fn swap_step_reaches_target_with_enough_input() {
+        let current = get_sqrt_price_at_tick(0).unwrap();
+        let target = get_sqrt_price_at_tick(-60).unwrap();
+        let step = compute_swap_step(current, target, 1_000_000_000, u64::MAX / 2, 30).unwrap();
+
+        assert_eq!(step.sqrt_price_next_x64, target);
+        assert_eq!(
+            step.amount_in as u128,
+            get_amount_a_delta(target, current, 1_000_000_000, true).unwrap()
+        );
+        assert!(step.fee_amount > 0);
+        assert!(step.amount_out < step.amount_in);
+    }
+
+    #[test]
+    fn swap_step_spends_all_input_before_target() {
+        let current = get_sqrt_price_at_tick(0).unwrap();
+        let target = get_sqrt_price_at_tick(600).unwrap();
+        let step = compute_swap_step(current, target, 1_000_000_000, 10_000, 30).unwrap();
+
+        assert!(step.sqrt_price_next_x64 > current && step.sqrt_price_next_x64 < target);
+        assert_eq!(step.amount_in + step.fee_amount, 10_000);
+        assert!(step.fee_amount >= 30);
+        assert!(step.amount_out <= step.amount_in);
+    }
+
+    #[test]
+    fn swap_step_without_fee_consumes_only_price_movement() {
+        let current = get_sqrt_price_at_tick(0).unwrap();
+        let target = get_sqrt_price_at_tick(-600).unwrap();
+        let step = compute_swap_step(current, target, 1_000_000_000, 10_000, 0).unwrap();
+
+        assert_eq!(step.amount_in + step.fee_amount, 10_000);
+        assert!(step.amount_out <= 10_000);
+    }
+}
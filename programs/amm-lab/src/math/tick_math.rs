@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+
+pub const MIN_TICK: i32 = -443636;
+pub const MAX_TICK: i32 = 443636;
+
+// get_sqrt_price_at_tick(MIN_TICK) 与 get_sqrt_price_at_tick(MAX_TICK)
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673521066979257578248091;
+
+
+// 计算 sqrt(1.0001^tick)，结果为 Q64.64 定点数
+// 先用预先算好的 1 / sqrt(1.0001)^(2^i) 累乘出 |tick| 对应的倒数，正 tick 再取倒数
+pub fn get_sqrt_price_at_tick(tick: i32) -> Result<u128> {
+    require!((MIN_TICK..=MAX_TICK).contains(&tick), ErrorCode::InvalidTick);
+
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio: u128 = if abs_tick & 0x1 != 0 {
+        0xfffcb933bd6fad37
+    } else {
+        1 << 64
+    };
+
+    if abs_tick & 0x2 != 0 {
+        ratio = (ratio * 0xfff97272373d4132) >> 64;
+    }
+    if abs_tick & 0x4 != 0 {
+        ratio = (ratio * 0xfff2e50f5f656932) >> 64;
+    }
+    if abs_tick & 0x8 != 0 {
+        ratio = (ratio * 0xffe5caca7e10e4e6) >> 64;
+    }
+    if abs_tick & 0x10 != 0 {
+        ratio = (ratio * 0xffcb9843d60f6159) >> 64;
+    }
+    if abs_tick & 0x20 != 0 {
+        ratio = (ratio * 0xff973b41fa98c081) >> 64;
+    }
+    if abs_tick & 0x40 != 0 {
+        ratio = (ratio * 0xff2ea16466c96a38) >> 64;
+    }
+    if abs_tick & 0x80 != 0 {
+        ratio = (ratio * 0xfe5dee046a99a2a8) >> 64;
+    }
+    if abs_tick & 0x100 != 0 {
+        ratio = (ratio * 0xfcbe86c7900a88ae) >> 64;
+    }
+    if abs_tick & 0x200 != 0 {
+        ratio = (ratio * 0xf987a7253ac41317) >> 64;
+    }
+    if abs_tick & 0x400 != 0 {
+        ratio = (ratio * 0xf3392b0822b70005) >> 64;
+    }
+    if abs_tick & 0x800 != 0 {
+        ratio = (ratio * 0xe7159475a2c29b74) >> 64;
+    }
+    if abs_tick & 0x1000 != 0 {
+        ratio = (ratio * 0xd097f3bdfd2022b8) >> 64;
+    }
+    if abs_tick & 0x2000 != 0 {
+        ratio = (ratio * 0xa9f746462d870fdf) >> 64;
+    }
+    if abs_tick & 0x4000 != 0 {
+        ratio = (ratio * 0x70d869a156d2a1b8) >> 64;
+    }
+    if abs_tick & 0x8000 != 0 {
+        ratio = (ratio * 0x31be135f97d08fd9) >> 64;
+    }
+    if abs_tick & 0x10000 != 0 {
+        ratio = (ratio * 0x9aa508b5b7a84e1) >> 64;
+    }
+    if abs_tick & 0x20000 != 0 {
+        ratio = (ratio * 0x5d6af8dedb8119) >> 64;
+    }
+    if abs_tick & 0x40000 != 0 {
+        ratio = (ratio * 0x2216e584f5fa) >> 64;
+    }
+
+    if tick > 0 {
+        ratio = u128::MAX / ratio;
+    }
+
+    Ok(ratio)
+}
+
+
+// 满足 get_sqrt_price_at_tick(tick) <= sqrt_price_x64 的最大 tick
+pub fn get_tick_at_sqrt_price(sqrt_price_x64: u128) -> Result<i32> {
+    require!(
+        (MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64), 
+        ErrorCode::InvalidSqrtPrice
+    );
+
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if get_sqrt_price_at_tick(mid)? <= sqrt_price_x64 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(low)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_price_bounds_match_tick_bounds() {
+        assert_eq!(get_sqrt_price_at_tick(0).unwrap(), 1 << 64);
+        assert_eq!(get_sqrt_price_at_tick(MIN_TICK).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(get_sqrt_price_at_tick(MAX_TICK).unwrap(), MAX_SQRT_PRICE_X64);
+        assert!(get_sqrt_price_at_tick(MIN_TICK - 1).is_err());
+        assert!(get_sqrt_price_at_tick(MAX_TICK + 1).is_err());
+    }
+
+    #[test]
+    fn sqrt_price_is_strictly_increasing() {
+        let mut previous = get_sqrt_price_at_tick(MIN_TICK).unwrap();
+        for tick in (MIN_TICK + 1..=MAX_TICK).step_by(997) {
+            let sqrt_price = get_sqrt_price_at_tick(tick).unwrap();
+            assert!(sqrt_price > previous);
+            previous = sqrt_price;
+        }
+    }
+
+    #[test]
+    fn tick_sqrt_price_round_trip() {
+        let ticks = [MIN_TICK, MIN_TICK + 1, -100_000, -60, -1, 0, 1, 60, 100_000, MAX_TICK - 1];
+        for tick in ticks.into_iter().chain((MIN_TICK..MAX_TICK).step_by(7919)) {
+            let sqrt_price = get_sqrt_price_at_tick(tick).unwrap();
+            assert_eq!(get_tick_at_sqrt_price(sqrt_price).unwrap(), tick);
+
+            // 落在两个 tick 之间的价格取较小的 tick
+            let next_sqrt_price = get_sqrt_price_at_tick(tick + 1).unwrap();
+            assert_eq!(get_tick_at_sqrt_price(next_sqrt_price - 1).unwrap(), tick);
+        }
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_rejects_out_of_range() {
+        assert!(get_tick_at_sqrt_price(MIN_SQRT_PRICE_X64 - 1).is_err());
+        assert!(get_tick_at_sqrt_price(MAX_SQRT_PRICE_X64).is_err());
+    }
+}
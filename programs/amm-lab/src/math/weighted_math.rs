@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use fixed::{consts::LN_2, types::{I64F64, U64F64}};
+
+use crate::errors::ErrorCode;
+
+
+// 自然对数，x 必须为正
+pub fn ln(x: U64F64) -> Result<I64F64> {
+    require!(x > 0, ErrorCode::MathOverflow);
+
+    // x = m * 2^k，其中 m ∈ [1, 2)
+    let bits = x.to_bits();
+    let k = 63 - bits.leading_zeros() as i32;
+    let m = if k >= 0 {
+        U64F64::from_bits(bits >> k)
+    } else {
+        U64F64::from_bits(bits << -k)
+    };
+
+    // ln(m) = 2 * atanh((m - 1) / (m + 1))，z 不超过 1/3，级数收敛很快
+    let z = I64F64::from_num((m - U64F64::ONE) / (m + U64F64::ONE));
+    let z_squared = z * z;
+    let mut term = z;
+    let mut sum = I64F64::ZERO;
+    let mut i = 1;
+    while term != 0 {
+        sum += term / I64F64::from_num(i);
+        term *= z_squared;
+        i += 2;
+    }
+
+    Ok(sum * 2 + I64F64::from_num(LN_2) * I64F64::from_num(k))
+}
+
+
+// 自然指数，结果超出 U64F64 范围时报错
+pub fn exp(x: I64F64) -> Result<U64F64> {
+    // x = k * ln2 + r，其中 r ∈ [0, ln2)
+    let ln_2 = I64F64::from_num(LN_2);
+    let k = (x / ln_2).floor().to_num::<i32>();
+    let r = U64F64::from_num(x - ln_2 * I64F64::from_num(k));
+
+    let mut term = U64F64::ONE;
+    let mut sum = U64F64::ONE;
+    let mut i = 1u32;
+    while term != 0 {
+        term = term * r / U64F64::from_num(i);
+        sum += term;
+        i += 1;
+    }
+
+    require!(k < 64, ErrorCode::MathOverflow);
+    Ok(if k >= 0 {
+        U64F64::from_bits(sum.to_bits() << k)
+    } else if k > -128 {
+        U64F64::from_bits(sum.to_bits() >> -k)
+    } else {
+        U64F64::ZERO
+    })
+}
+
+
+// base^exponent = exp(exponent * ln(base))
+pub fn pow(base: U64F64, exponent: I64F64) -> Result<U64F64> {
+    if base == 0 {
+        return Ok(U64F64::ZERO);
+    }
+
+    exp(ln(base)?.checked_mul(exponent).ok_or(error!(ErrorCode::MathOverflow))?)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-12;
+
+    fn assert_close(actual: f64, expected: f64) {
+        let error = (actual - expected).abs() / expected.abs().max(1.0);
+        assert!(error < TOLERANCE, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn ln_matches_reference_values() {
+        assert_eq!(ln(U64F64::ONE).unwrap(), I64F64::ZERO);
+        for x in [0.001f64, 0.25, 0.5, 0.9999, 1.5, 2.0, std::f64::consts::E, 10.0, 123_456.789, 1e18] {
+            let actual = ln(U64F64::from_num(x)).unwrap().to_num::<f64>();
+            assert_close(actual, x.ln());
+        }
+        assert!(ln(U64F64::ZERO).is_err());
+    }
+
+    #[test]
+    fn exp_matches_reference_values() {
+        assert_eq!(exp(I64F64::ZERO).unwrap(), U64F64::ONE);
+        for x in [-20.0f64, -1.0, -0.5, 0.001, 0.5, 1.0, 5.0, 30.0, 43.0] {
+            let actual = exp(I64F64::from_num(x)).unwrap().to_num::<f64>();
+            assert_close(actual, x.exp());
+        }
+        assert!(exp(I64F64::from_num(45)).is_err());
+        assert_eq!(exp(I64F64::from_num(-100)).unwrap(), U64F64::ZERO);
+    }
+
+    #[test]
+    fn pow_matches_reference_values() {
+        let cases = [(4.0, 0.5), (2.0, 3.0), (1.2, 0.25), (0.8, 4.0), (1_000.0, 0.2), (0.5, -2.0)];
+        for (base, exponent) in cases {
+            let actual = pow(U64F64::from_num(base), I64F64::from_num(exponent)).unwrap();
+            assert_close(actual.to_num::<f64>(), f64::powf(base, exponent));
+        }
+        assert_eq!(pow(U64F64::ZERO, I64F64::from_num(0.5)).unwrap(), U64F64::ZERO);
+    }
+}
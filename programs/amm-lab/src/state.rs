@@ -1,12 +1,27 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{MAX_FARM_REWARDS, REWARD_PRECISION, TICK_ARRAY_SIZE}, 
+    errors::ErrorCode, 
+    math::{big_num::{to_u128, to_u64, U256}, liquidity_math::add_delta}
+};
+
 
 #[account]
 #[derive(InitSpace)]
 pub struct Amm {
     pub id: Pubkey,
     pub admin: Pubkey,
-    pub fee: u16
+    pub fee: u16,
+    // 新建池子的流动性代币精度与首笔存款锁定的最低流动性
+    pub lp_decimals: u8,
+    pub minimum_liquidity: u64,
+    // 新建池子的流动性代币元数据 URI，由管理员配置
+    #[max_len(200)]
+    pub lp_metadata_uri: String,
+    // 协议费开关，开启后恒定乘积池 sqrt(k) 增长的 1/6 以流动性代币的形式铸造给 treasury
+    pub protocol_fee_on: bool,
+    pub treasury: Pubkey
 }
 
 
@@ -15,5 +30,946 @@ pub struct Amm {
 pub struct Pool {
     pub amm: Pubkey,
     pub a_mint: Pubkey,
-    pub b_mint: Pubkey
+    pub b_mint: Pubkey,
+    // 池子内部记账的储备量，定价只依赖这两个值，直接转入金库的代币不会影响价格
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    // 创建时从 Amm 继承的流动性配置
+    pub lp_decimals: u8,
+    pub minimum_liquidity: u64,
+    pub curve_type: CurveType,
+    // 稳定曲线的放大系数 A，在 [ramp_start_ts, ramp_stop_ts] 内从 initial_amp 线性过渡到 target_amp
+    pub initial_amp: u64,
+    pub target_amp: u64,
+    pub ramp_start_ts: i64,
+    pub ramp_stop_ts: i64,
+    // 加权曲线的两侧权重，之和为 WEIGHT_ONE
+    pub weight_a: u64,
+    pub weight_b: u64,
+    // 恒定和曲线的兑换比例：1 个 a 兑换 sum_rate / SUM_RATE_ONE 个 b（按最小单位计）
+    // 调整时在 [ramp_start_ts, ramp_stop_ts] 内从 initial_sum_rate 线性过渡到 sum_rate，两种曲线不会同时使用这段时间
+    pub initial_sum_rate: u64,
+    pub sum_rate: u64,
+    // 紧急状态下只允许按金库余额等比例赎回，其余操作全部拒绝
+    pub emergency: bool,
+    // 上次存取流动性后的 reserve_a * reserve_b，协议费关闭时为 0
+    pub k_last: u128,
+    // 累计价格预言机：每次兑换、存取前把上次更新以来的 价格 * 秒数 累加进来
+    // 价格为 U64F64 原始位，累计值以小端 U256 存储，价格再高也要上亿年才会回绕
+    pub price_a_cumulative: [u64; 4],
+    pub price_b_cumulative: [u64; 4],
+    pub last_update_ts: i64,
+    // 创建观测缓冲区之后，每次兑换都必须传入并写入观测
+    pub observations_enabled: bool,
+    // 兑换时对照的参考价格账户，Pubkey::default() 表示不启用
+    pub price_feed: Pubkey,
+    // 成交价相对参考价格允许的最大偏离（基点）与参考价格的最长有效时间（秒）
+    pub max_price_deviation: u16,
+    pub max_price_age: i64
+}
+
+impl Pool {
+    pub fn current_amp(&self, now: i64) -> u64 {
+        self.ramp(self.initial_amp, self.target_amp, now)
+    }
+
+    pub fn current_sum_rate(&self, now: i64) -> u64 {
+        self.ramp(self.initial_sum_rate, self.sum_rate, now)
+    }
+
+    // 在 [ramp_start_ts, ramp_stop_ts] 内从 initial 线性过渡到 target
+    fn ramp(&self, initial: u64, target: u64, now: i64) -> u64 {
+        if now >= self.ramp_stop_ts || self.ramp_stop_ts <= self.ramp_start_ts {
+            return target;
+        }
+
+        let elapsed = now.saturating_sub(self.ramp_start_ts).max(0) as i128;
+        let duration = (self.ramp_stop_ts - self.ramp_start_ts) as i128;
+        let delta = target as i128 - initial as i128;
+
+        (initial as i128 + delta * elapsed / duration) as u64
+    }
+
+    // 自上次记录 k_last 以来 sqrt(k) 的增长中属于协议的 1/6，以流动性份额表示：
+    // supply * (sqrt(k) - sqrt(k_last)) / (5 * sqrt(k) + sqrt(k_last))
+    pub fn protocol_fee_liquidity(&self, total_supply: u64) -> Result<u64> {
+        if self.curve_type != CurveType::ConstantProduct || self.k_last == 0 {
+            return Ok(0);
+        }
+
+        let root_k = U256::from(self.reserve_a as u128 * self.reserve_b as u128).integer_sqrt();
+        let root_k_last = U256::from(self.k_last).integer_sqrt();
+        if root_k <= root_k_last {
+            return Ok(0);
+        }
+
+        let liquidity = U256::from(total_supply) * (root_k - root_k_last) / (root_k * 5 + root_k_last);
+        to_u64(to_u128(liquidity)?)
+    }
+
+    pub fn update_k_last(&mut self, protocol_fee_on: bool) {
+        self.k_last = if protocol_fee_on && self.curve_type == CurveType::ConstantProduct {
+            self.reserve_a as u128 * self.reserve_b as u128
+        } else {
+            0
+        };
+    }
+}
+
+
+// 两次观测之间的时间加权平均价格（U64F64 原始位），累计值按回绕相减
+pub fn twap(cumulative_start: [u64; 4], ts_start: i64, cumulative_end: [u64; 4], ts_end: i64) -> Result<u128> {
+    require!(ts_end > ts_start, ErrorCode::InvalidObservation);
+    let (delta, _) = U256(cumulative_end).overflowing_sub(U256(cumulative_start));
+    to_u128(delta / U256::from(ts_end - ts_start))
+}
+
+
+// 由 authority 推送的参考价格，测试中可以直接用本地创建的账户替代外部预言机
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeed {
+    pub id: Pubkey,
+    pub authority: Pubkey,
+    // 以 b 计价的 a 的价格（按最小单位计，U64F64 原始位）
+    pub price: u128,
+    pub last_update_ts: i64
+}
+
+impl PriceFeed {
+    // a_amount 个 a 与 b_amount 个 b 成交时，成交价相对参考价格的偏离不能超过 max_deviation 基点
+    pub fn check_price(&self, a_amount: u64, b_amount: u64, max_deviation: u16, max_age: i64, now: i64) -> Result<()> {
+        let execution = U256::from(b_amount) << 64;
+        let reference = U256::from(self.price) * U256::from(a_amount);
+        self.check_deviation(execution, reference, max_deviation, max_age, now)
+    }
+
+    // 池子的边际价格（U64F64 原始位）相对参考价格的偏离不能超过 max_deviation 基点
+    pub fn check_spot_price(&self, spot_price: u128, max_deviation: u16, max_age: i64, now: i64) -> Result<()> {
+        self.check_deviation(U256::from(spot_price), U256::from(self.price), max_deviation, max_age, now)
+    }
+
+    fn check_deviation(&self, execution: U256, reference: U256, max_deviation: u16, max_age: i64, now: i64) -> Result<()> {
+        require!(now - self.last_update_ts <= max_age, ErrorCode::StalePrice);
+
+        let deviation = if execution > reference { execution - reference } else { reference - execution };
+        require!(
+            deviation * U256::from(10000) <= reference * U256::from(max_deviation), 
+            ErrorCode::PriceDeviationTooLarge
+        );
+
+        Ok(())
+    }
+}
+
+
+// 池子的价格观测环形缓冲区的头部，由兑换写入，任何人都可以付费扩容
+// 观测按固定大小的槽位紧跟在头部之后，兑换时直接借用账户数据，不必把整个缓冲区反序列化到堆上
+#[account(zero_copy)]
+pub struct Observations {
+    pub pool: Pubkey,
+    // 最近一次写入的位置
+    pub index: u16,
+    // 正在使用的长度，写到末尾时才启用扩容后的全部空间
+    pub cardinality: u16,
+    // 已分配的槽位数，只能增大
+    pub capacity: u16,
+    pub padding: [u8; 10]
+}
+
+impl Observations {
+    // 鉴别码与头部的长度，槽位从这里开始
+    pub const HEADER_LEN: usize = 8 + std::mem::size_of::<Observations>();
+
+    pub fn space(capacity: usize) -> usize {
+        Self::HEADER_LEN + capacity * std::mem::size_of::<Observation>()
+    }
+
+    // 账户数据中能容纳的槽位数
+    pub fn slots(data_len: usize) -> usize {
+        data_len.saturating_sub(Self::HEADER_LEN) / std::mem::size_of::<Observation>()
+    }
+
+    // 从整个账户数据中取出头部与槽位，鉴别码由 AccountLoader 校验
+    pub fn split(data: &[u8]) -> (&Observations, &[Observation]) {
+        let end = Self::space(Self::slots(data.len()));
+        let (header, observations) = data[8..end].split_at(Self::HEADER_LEN - 8);
+        (bytemuck::from_bytes(header), bytemuck::cast_slice(observations))
+    }
+
+    pub fn split_mut(data: &mut [u8]) -> (&mut Observations, &mut [Observation]) {
+        let end = Self::space(Self::slots(data.len()));
+        let (header, observations) = data[8..end].split_at_mut(Self::HEADER_LEN - 8);
+        (bytemuck::from_bytes_mut(header), bytemuck::cast_slice_mut(observations))
+    }
+
+    pub fn latest(&self, observations: &[Observation]) -> Observation {
+        observations[self.index as usize]
+    }
+
+    // 写入 now 时刻的累计价格，同一秒内只写一次；流动性以写入时的流动性代币总量近似上一段时间的值
+    pub fn write(
+        &mut self, 
+        observations: &mut [Observation], 
+        now: i64, 
+        price_a_cumulative: [u64; 4], 
+        price_b_cumulative: [u64; 4], 
+        liquidity: u64
+    ) {
+        let last = self.latest(observations);
+        if now <= last.timestamp {
+            return;
+        }
+
+        if self.index + 1 == self.cardinality && (self.cardinality as usize) < observations.len() {
+            self.cardinality = observations.len() as u16;
+        }
+        self.index = (self.index + 1) % self.cardinality;
+        observations[self.index as usize] = Observation {
+            liquidity_cumulative: last.liquidity_cumulative
+                .wrapping_add(liquidity as u128 * (now - last.timestamp) as u128),
+            timestamp: now,
+            price_a_cumulative,
+            price_b_cumulative,
+            initialized: 1,
+            padding: [0; 7]
+        };
+    }
+
+    // 目标时刻的累计值，落在两条观测之间时线性插值，current 为当前时刻的虚拟观测
+    pub fn observe_at(&self, observations: &[Observation], target: i64, current: &Observation) -> Result<Observation> {
+        if target >= current.timestamp {
+            return Ok(*current);
+        }
+
+        // 环形缓冲区未写满时最旧的观测在 0 号位置
+        let cardinality = self.cardinality as usize;
+        let next = (self.index as usize + 1) % cardinality;
+        let (oldest, count) = if observations[next].initialized != 0 {
+            (next, cardinality)
+        } else {
+            (0, self.index as usize + 1)
+        };
+        let at = |k: usize| -> Observation {
+            if k == count { *current } else { observations[(oldest + k) % cardinality] }
+        };
+        require!(at(0).timestamp <= target, ErrorCode::ObservationTooOld);
+
+        // 二分查找最后一条不晚于 target 的观测
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if at(mid).timestamp <= target {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        let before = at(low);
+        if before.timestamp == target {
+            return Ok(before);
+        }
+        Ok(before.interpolate(&at(low + 1), target))
+    }
+}
+
+
+// 观测槽位按 Pod 直接映射到账户数据，同时作为 observe 的返回值序列化
+// 字段按对齐从大到小排列，保证槽位没有隐式填充
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct Observation {
+    // 流动性代币总量 * 秒数的累计值
+    pub liquidity_cumulative: u128,
+    pub timestamp: i64,
+    // 与 Pool 相同的小端 U256 累计价格
+    pub price_a_cumulative: [u64; 4],
+    pub price_b_cumulative: [u64; 4],
+    pub initialized: u8,
+    pub padding: [u8; 7]
+}
+
+impl Observation {
+    fn interpolate(&self, after: &Observation, target: i64) -> Observation {
+        let total = (after.timestamp - self.timestamp) as u128;
+        let elapsed = (target - self.timestamp) as u128;
+        // 先乘后除，避免间隔较长时丢失精度
+        let lerp = |start: u128, end: u128| {
+            let delta = U256::from(end.wrapping_sub(start)) * U256::from(elapsed) / U256::from(total);
+            start.wrapping_add(delta.low_u128())
+        };
+        let lerp_price = |start: [u64; 4], end: [u64; 4]| {
+            let (delta, _) = U256(end).overflowing_sub(U256(start));
+            let (value, _) = U256(start).overflowing_add(delta * U256::from(elapsed) / U256::from(total));
+            value.0
+        };
+
+        Observation {
+            liquidity_cumulative: lerp(self.liquidity_cumulative, after.liquidity_cumulative),
+            timestamp: target,
+            price_a_cumulative: lerp_price(self.price_a_cumulative, after.price_a_cumulative),
+            price_b_cumulative: lerp_price(self.price_b_cumulative, after.price_b_cumulative),
+            initialized: 1,
+            padding: [0; 7]
+        }
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct MultiPool {
+    pub amm: Pubkey,
+    // 用于推导池子地址，同一组代币可以创建多个池子
+    pub id: Pubkey,
+    // 创建者在首笔存款之前逐个加入成员代币
+    pub creator: Pubkey,
+    // 仅支持稳定曲线与加权曲线
+    pub curve_type: CurveType,
+    pub amp: u64,
+    pub lp_decimals: u8,
+    pub minimum_liquidity: u64,
+    #[max_len(8)]
+    pub tokens: Vec<PoolToken>
+}
+
+impl MultiPool {
+    pub fn token_index(&self, mint: &Pubkey) -> Result<usize> {
+        self.tokens
+            .iter()
+            .position(|token| token.mint == *mint)
+            .ok_or(error!(ErrorCode::InvalidMint))
+    }
+
+    pub fn reserves(&self) -> Vec<u64> {
+        self.tokens.iter().map(|token| token.reserve).collect()
+    }
+
+    pub fn weights(&self) -> Vec<u64> {
+        self.tokens.iter().map(|token| token.weight).collect()
+    }
+
+    pub fn decimals(&self) -> Vec<u8> {
+        self.tokens.iter().map(|token| token.decimals).collect()
+    }
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PoolToken {
+    pub mint: Pubkey,
+    // 由池子持有的关联代币账户
+    pub vault: Pubkey,
+    pub decimals: u8,
+    // 加权曲线下的权重，所有成员之和为 WEIGHT_ONE
+    pub weight: u64,
+    pub reserve: u64
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum CurveType {
+    ConstantProduct,
+    Stable,
+    Weighted,
+    ConstantSum
+}
+
+impl CurveType {
+    // 两币池的池子、authority 与流动性代币地址都带上曲线类型，同一交易对的每种曲线各有一个池子
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            CurveType::ConstantProduct => &[0],
+            CurveType::Stable => &[1],
+            CurveType::Weighted => &[2],
+            CurveType::ConstantSum => &[3]
+        }
+    }
+}
+
+
+// 创建两币池时选择的曲线及其参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum PoolCurve {
+    ConstantProduct,
+    Stable { amp: u64 },
+    Weighted { weight_a: u64 },
+    ConstantSum { sum_rate: u64 }
+}
+
+impl PoolCurve {
+    pub fn curve_type(&self) -> CurveType {
+        match self {
+            PoolCurve::ConstantProduct => CurveType::ConstantProduct,
+            PoolCurve::Stable { .. } => CurveType::Stable,
+            PoolCurve::Weighted { .. } => CurveType::Weighted,
+            PoolCurve::ConstantSum { .. } => CurveType::ConstantSum
+        }
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityLock {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    // 锁定的流动性总量与已经取回的数量
+    pub amount: u64,
+    pub withdrawn: u64,
+    pub start_ts: i64,
+    pub unlock_ts: i64,
+    // 为 true 时在 start_ts 到 unlock_ts 之间线性释放，否则到期一次性释放
+    pub linear: bool
+}
+
+impl LiquidityLock {
+    // 截至 now 已释放（含已取回）的数量
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now >= self.unlock_ts {
+            self.amount
+        } else if self.linear && now > self.start_ts {
+            (self.amount as u128 * (now - self.start_ts) as u128 
+                / (self.unlock_ts - self.start_ts) as u128) as u64
+        } else {
+            0
+        }
+    }
+
+    // 当前可以取回的数量
+    pub fn unlockable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.withdrawn)
+    }
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RewardInfo {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub emission_per_second: u64,
+    // 每份质押累计获得的奖励，放大 REWARD_PRECISION 倍
+    pub reward_per_share: u128,
+    // 无人质押期间产生的奖励，留到下次有人质押时再分配
+    pub undistributed: u128
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct Farm {
+    pub pool: Pubkey,
+    pub mint_liquidity: Pubkey,
+    pub total_staked: u64,
+    pub last_update_ts: i64,
+    #[max_len(3)]
+    pub rewards: Vec<RewardInfo>
+}
+
+impl Farm {
+    // 按经过的时间累加每份质押的奖励
+    pub fn update_rewards(&mut self, now: i64) {
+        if now <= self.last_update_ts {
+            return;
+        }
+
+        let elapsed = (now - self.last_update_ts) as u128;
+        for reward in self.rewards.iter_mut() {
+            let emitted = reward.emission_per_second as u128 * elapsed;
+            if self.total_staked == 0 {
+                reward.undistributed = reward.undistributed.checked_add(emitted).unwrap();
+                continue;
+            }
+
+            let emitted = emitted.checked_add(reward.undistributed).unwrap();
+            reward.undistributed = 0;
+            reward.reward_per_share = reward.reward_per_share
+                .checked_add(
+                    emitted.checked_mul(REWARD_PRECISION).unwrap() / self.total_staked as u128
+                )
+                .unwrap();
+        }
+
+        self.last_update_ts = now;
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserStake {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    // 上次结算时已计入的奖励与尚未领取的奖励，下标与 Farm.rewards 对应
+    pub reward_debts: [u128; MAX_FARM_REWARDS],
+    pub pending_rewards: [u64; MAX_FARM_REWARDS]
+}
+
+impl UserStake {
+    // 将上次结算以来的奖励计入待领取，调用前需先更新 farm
+    pub fn settle(&mut self, farm: &Farm) {
+        for (i, reward) in farm.rewards.iter().enumerate() {
+            let accrued = self.amount as u128 * reward.reward_per_share / REWARD_PRECISION;
+            let pending = accrued.saturating_sub(self.reward_debts[i]) as u64;
+            self.pending_rewards[i] = self.pending_rewards[i].checked_add(pending).unwrap();
+            self.reward_debts[i] = accrued;
+        }
+    }
+
+    // 质押数量变化后重新记录已计入的奖励
+    pub fn reset_debts(&mut self, farm: &Farm) {
+        for (i, reward) in farm.rewards.iter().enumerate() {
+            self.reward_debts[i] = self.amount as u128 * reward.reward_per_share / REWARD_PRECISION;
+        }
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct ClPool {
+    pub amm: Pubkey,
+    pub a_mint: Pubkey,
+    pub b_mint: Pubkey,
+    pub tick_spacing: u16,
+    // 当前价格的平方根，Q64.64 定点数
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    // 当前价格所在区间内的活跃流动性
+    pub liquidity: u128,
+    // 每单位流动性累计的手续费，Q64.64 定点数
+    pub fee_growth_global_a_x64: u128,
+    pub fee_growth_global_b_x64: u128
+}
+
+
+#[zero_copy]
+#[derive(Default)]
+pub struct Tick {
+    // 从左向右穿过该 tick 时活跃流动性的变化量
+    pub liquidity_net: i128,
+    pub liquidity_gross: u128,
+    // 该 tick 另一侧累计的手续费，Q64.64 定点数
+    pub fee_growth_outside_a_x64: u128,
+    pub fee_growth_outside_b_x64: u128,
+    pub initialized: u8,
+    pub padding: [u8; 15]
+}
+
+impl Tick {
+    // 更新 tick 上的流动性，返回 tick 是否在已初始化与未初始化之间切换
+    pub fn update(
+        &mut self, 
+        tick_index: i32, 
+        tick_current: i32, 
+        liquidity_delta: i128, 
+        fee_growth_global_a_x64: u128, 
+        fee_growth_global_b_x64: u128, 
+        upper: bool
+    ) -> Result<bool> {
+        let liquidity_gross_before = self.liquidity_gross;
+        let liquidity_gross_after = add_delta(liquidity_gross_before, liquidity_delta)?;
+        let flipped = (liquidity_gross_after == 0) != (liquidity_gross_before == 0);
+
+        if liquidity_gross_before == 0 {
+            // 约定 tick 初始化之前的手续费都产生在 tick 以下
+            if tick_index <= tick_current {
+                self.fee_growth_outside_a_x64 = fee_growth_global_a_x64;
+                self.fee_growth_outside_b_x64 = fee_growth_global_b_x64;
+            }
+            self.initialized = 1;
+        }
+
+        self.liquidity_gross = liquidity_gross_after;
+        self.liquidity_net = if upper {
+            self.liquidity_net.checked_sub(liquidity_delta)
+        } else {
+            self.liquidity_net.checked_add(liquidity_delta)
+        }.ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok(flipped)
+    }
+
+    // 价格穿过 tick 时翻转两侧的手续费记录，返回 liquidity_net
+    pub fn cross(&mut self, fee_growth_global_a_x64: u128, fee_growth_global_b_x64: u128) -> i128 {
+        self.fee_growth_outside_a_x64 = fee_growth_global_a_x64.wrapping_sub(self.fee_growth_outside_a_x64);
+        self.fee_growth_outside_b_x64 = fee_growth_global_b_x64.wrapping_sub(self.fee_growth_outside_b_x64);
+
+        self.liquidity_net
+    }
+
+    pub fn clear(&mut self) {
+        *self = Tick::default();
+    }
+}
+
+
+// 区间 [tick_lower, tick_upper) 内每单位流动性累计的手续费
+pub fn fee_growth_inside(
+    lower: &Tick, 
+    upper: &Tick, 
+    tick_lower: i32, 
+    tick_upper: i32, 
+    tick_current: i32, 
+    fee_growth_global_a_x64: u128, 
+    fee_growth_global_b_x64: u128
+) -> (u128, u128) {
+    let (below_a, below_b) = if tick_current >= tick_lower {
+        (lower.fee_growth_outside_a_x64, lower.fee_growth_outside_b_x64)
+    } else {
+        (
+            fee_growth_global_a_x64.wrapping_sub(lower.fee_growth_outside_a_x64),
+            fee_growth_global_b_x64.wrapping_sub(lower.fee_growth_outside_b_x64)
+        )
+    };
+
+    let (above_a, above_b) = if tick_current < tick_upper {
+        (upper.fee_growth_outside_a_x64, upper.fee_growth_outside_b_x64)
+    } else {
+        (
+            fee_growth_global_a_x64.wrapping_sub(upper.fee_growth_outside_a_x64),
+            fee_growth_global_b_x64.wrapping_sub(upper.fee_growth_outside_b_x64)
+        )
+    };
+
+    (
+        fee_growth_global_a_x64.wrapping_sub(below_a).wrapping_sub(above_a),
+        fee_growth_global_b_x64.wrapping_sub(below_b).wrapping_sub(above_b)
+    )
+}
+
+
+#[account(zero_copy)]
+pub struct TickArray {
+    pub pool: Pubkey,
+    pub start_tick_index: i32,
+    pub padding: [u8; 12],
+    pub ticks: [Tick; TICK_ARRAY_SIZE]
+}
+
+impl TickArray {
+    // 每个 tick 数组覆盖的 tick 宽度
+    pub fn width(tick_spacing: u16) -> i32 {
+        tick_spacing as i32 * TICK_ARRAY_SIZE as i32
+    }
+
+    // 包含 tick_index 的数组的起始 tick
+    pub fn start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+        let width = Self::width(tick_spacing);
+        tick_index.div_euclid(width) * width
+    }
+
+    pub fn contains(&self, tick_index: i32, tick_spacing: u16) -> bool {
+        tick_index >= self.start_tick_index 
+            && tick_index < self.start_tick_index + Self::width(tick_spacing)
+    }
+
+    pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        require!(tick_index % tick_spacing as i32 == 0, ErrorCode::InvalidTick);
+        require!(self.contains(tick_index, tick_spacing), ErrorCode::InvalidTickArray);
+
+        Ok(((tick_index - self.start_tick_index) / tick_spacing as i32) as usize)
+    }
+
+    // 在本数组内沿兑换方向查找下一个已初始化的 tick，a_to_b 时包含当前 tick
+    pub fn next_initialized_tick(&self, tick_current: i32, tick_spacing: u16, a_to_b: bool) -> Option<i32> {
+        let spacing = tick_spacing as i32;
+        let offset = (tick_current - self.start_tick_index).div_euclid(spacing);
+
+        if a_to_b {
+            if offset < 0 {
+                return None;
+            }
+            (0..=offset.min(TICK_ARRAY_SIZE as i32 - 1))
+                .rev()
+                .find(|&i| self.ticks[i as usize].initialized != 0)
+                .map(|i| self.start_tick_index + i * spacing)
+        } else {
+            ((offset + 1).max(0)..TICK_ARRAY_SIZE as i32)
+                .find(|&i| self.ticks[i as usize].initialized != 0)
+                .map(|i| self.start_tick_index + i * spacing)
+        }
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub pool: Pubkey,
+    // 持有该 NFT 的账户即为仓位所有者
+    pub position_mint: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    // 上次结算时区间内每单位流动性的累计手续费
+    pub fee_growth_inside_a_last_x64: u128,
+    pub fee_growth_inside_b_last_x64: u128,
+    // 已结算但尚未领取的手续费
+    pub tokens_owed_a: u64,
+    pub tokens_owed_b: u64
+}
+
+impl Position {
+    // 结算手续费并更新仓位流动性
+    pub fn update(
+        &mut self, 
+        liquidity_delta: i128, 
+        fee_growth_inside_a_x64: u128, 
+        fee_growth_inside_b_x64: u128
+    ) -> Result<()> {
+        let owed_a = (U256::from(fee_growth_inside_a_x64.wrapping_sub(self.fee_growth_inside_a_last_x64)) 
+            * U256::from(self.liquidity)) >> 64;
+        let owed_b = (U256::from(fee_growth_inside_b_x64.wrapping_sub(self.fee_growth_inside_b_last_x64)) 
+            * U256::from(self.liquidity)) >> 64;
+
+        // 与 Uniswap v3 一致，超出 u64 的手续费会被截断，需要在此之前领取
+        self.tokens_owed_a = self.tokens_owed_a.wrapping_add(owed_a.low_u64());
+        self.tokens_owed_b = self.tokens_owed_b.wrapping_add(owed_b.low_u64());
+
+        self.liquidity = add_delta(self.liquidity, liquidity_delta)?;
+        self.fee_growth_inside_a_last_x64 = fee_growth_inside_a_x64;
+        self.fee_growth_inside_b_last_x64 = fee_growth_inside_b_x64;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(amount: u64, linear: bool) -> LiquidityLock {
+        LiquidityLock {
+            pool: Pubkey::default(),
+            owner: Pubkey::default(),
+            amount,
+            withdrawn: 0,
+            start_ts: 1_000,
+            unlock_ts: 2_000,
+            linear
+        }
+    }
+
+    #[test]
+    fn cliff_lock_releases_everything_at_unlock() {
+        let lock = lock(1_000, false);
+        assert_eq!(lock.vested_amount(1_000), 0);
+        assert_eq!(lock.vested_amount(1_999), 0);
+        assert_eq!(lock.vested_amount(2_000), 1_000);
+        assert_eq!(lock.vested_amount(5_000), 1_000);
+    }
+
+    #[test]
+    fn linear_lock_releases_proportionally() {
+        let lock = lock(1_000, true);
+        assert_eq!(lock.vested_amount(500), 0);
+        assert_eq!(lock.vested_amount(1_000), 0);
+        assert_eq!(lock.vested_amount(1_250), 250);
+        assert_eq!(lock.vested_amount(1_999), 999);
+        assert_eq!(lock.vested_amount(2_000), 1_000);
+    }
+
+    #[test]
+    fn unlockable_amount_excludes_withdrawn() {
+        let mut lock = lock(1_000, true);
+        lock.withdrawn = 400;
+        assert_eq!(lock.unlockable_amount(1_250), 0);
+        assert_eq!(lock.unlockable_amount(1_500), 100);
+        assert_eq!(lock.unlockable_amount(2_000), 600);
+    }
+
+    #[test]
+    fn linear_vesting_does_not_overflow() {
+        let mut lock = lock(u64::MAX, true);
+        lock.start_ts = 0;
+        lock.unlock_ts = 1_000_000;
+        assert_eq!(lock.vested_amount(500_000), u64::MAX / 2);
+    }
+
+    fn farm(total_staked: u64, emissions: &[u64]) -> Farm {
+        Farm {
+            pool: Pubkey::default(),
+            mint_liquidity: Pubkey::default(),
+            total_staked,
+            last_update_ts: 1_000,
+            rewards: emissions
+                .iter()
+                .map(|&emission_per_second| RewardInfo { emission_per_second, ..Default::default() })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn farm_accumulates_reward_per_share() {
+        let mut farm = farm(500, &[10, 3]);
+        farm.update_rewards(1_100);
+        assert_eq!(farm.last_update_ts, 1_100);
+        assert_eq!(farm.rewards[0].reward_per_share, 1_000 * REWARD_PRECISION / 500);
+        assert_eq!(farm.rewards[1].reward_per_share, 300 * REWARD_PRECISION / 500);
+
+        // 时间没有前进时不重复累加
+        farm.update_rewards(1_100);
+        farm.update_rewards(1_050);
+        assert_eq!(farm.last_update_ts, 1_100);
+        assert_eq!(farm.rewards[0].reward_per_share, 1_000 * REWARD_PRECISION / 500);
+    }
+
+    #[test]
+    fn farm_carries_emissions_while_nothing_staked() {
+        let mut farm = farm(0, &[10]);
+        farm.update_rewards(1_100);
+        assert_eq!(farm.rewards[0].reward_per_share, 0);
+        assert_eq!(farm.rewards[0].undistributed, 1_000);
+
+        // 中途调整发放速度不会影响已经产生的奖励
+        farm.rewards[0].emission_per_second = 20;
+        farm.update_rewards(1_200);
+        assert_eq!(farm.rewards[0].undistributed, 3_000);
+
+        farm.total_staked = 100;
+        farm.update_rewards(1_300);
+        assert_eq!(farm.rewards[0].undistributed, 0);
+        assert_eq!(farm.rewards[0].reward_per_share, 5_000 * REWARD_PRECISION / 100);
+    }
+
+    #[test]
+    fn twap_handles_wrapped_accumulator() {
+        let price = U256::from(3u128 << 64);
+        let start = U256::MAX - price * U256::from(5);
+        let (end, _) = start.overflowing_add(price * U256::from(10));
+        assert_eq!(twap(start.0, 100, end.0, 110).unwrap(), 3u128 << 64);
+        assert!(twap(start.0, 110, end.0, 110).is_err());
+    }
+
+    fn cumulative(value: u128) -> [u64; 4] {
+        U256::from(value).0
+    }
+
+    // 时刻 ts 的累计价格取 ts * 10，流动性恒为 1
+    fn observations(capacity: usize, timestamps: &[i64]) -> (Observations, Vec<Observation>) {
+        let mut observations = vec![Observation::default(); capacity];
+        observations[0] = Observation {
+            timestamp: 100,
+            price_a_cumulative: cumulative(1_000),
+            price_b_cumulative: cumulative(1_000),
+            initialized: 1,
+            ..Default::default()
+        };
+        let mut header = Observations {
+            pool: Pubkey::default(),
+            index: 0,
+            cardinality: 1,
+            capacity: capacity as u16,
+            padding: [0; 10]
+        };
+        for &ts in timestamps {
+            header.write(&mut observations, ts, cumulative(ts as u128 * 10), cumulative(ts as u128 * 10), 1);
+        }
+        (header, observations)
+    }
+
+    fn current(ts: i64) -> Observation {
+        Observation {
+            liquidity_cumulative: ts as u128 - 100,
+            timestamp: ts,
+            price_a_cumulative: cumulative(ts as u128 * 10),
+            price_b_cumulative: cumulative(ts as u128 * 10),
+            initialized: 1,
+            padding: [0; 7]
+        }
+    }
+
+    #[test]
+    fn observe_at_returns_exact_and_interpolated_values() {
+        let (header, observations) = observations(4, &[110, 120]);
+        let current = current(130);
+
+        for target in [100, 105, 110, 115, 120, 125, 130, 140] {
+            let observation = header.observe_at(&observations, target, &current).unwrap();
+            let expected = target.min(130);
+            assert_eq!(observation.timestamp, expected);
+            assert_eq!(observation.price_a_cumulative, cumulative(expected as u128 * 10));
+            assert_eq!(observation.liquidity_cumulative, expected as u128 - 100);
+        }
+        assert!(header.observe_at(&observations, 99, &current).is_err());
+    }
+
+    #[test]
+    fn observe_at_follows_wrapped_buffer() {
+        let (header, observations) = observations(3, &[110, 120, 130, 140]);
+        assert_eq!(header.cardinality, 3);
+        assert_eq!(header.latest(&observations).timestamp, 140);
+
+        let current = current(150);
+        assert!(header.observe_at(&observations, 115, &current).is_err());
+        for target in [120, 125, 135, 140, 145] {
+            let observation = header.observe_at(&observations, target, &current).unwrap();
+            assert_eq!(observation.price_b_cumulative, cumulative(target as u128 * 10));
+        }
+    }
+
+    #[test]
+    fn write_skips_same_second() {
+        let (mut header, mut observations) = observations(4, &[110]);
+        header.write(&mut observations, 110, cumulative(5), cumulative(5), 1);
+        header.write(&mut observations, 105, cumulative(5), cumulative(5), 1);
+        assert_eq!(header.index, 1);
+        assert_eq!(header.latest(&observations).price_a_cumulative, cumulative(1_100));
+    }
+
+    #[test]
+    fn grown_slots_join_after_the_write_reaches_the_end() {
+        let (mut header, mut observations) = observations(2, &[110, 120]);
+        assert_eq!((header.index, header.cardinality), (0, 2));
+
+        // 扩容后的槽位为零，写到当前末尾之后才启用
+        observations.resize(4, Observation::default());
+        header.write(&mut observations, 130, cumulative(1_300), cumulative(1_300), 1);
+        assert_eq!((header.index, header.cardinality), (1, 2));
+        header.write(&mut observations, 140, cumulative(1_400), cumulative(1_400), 1);
+        assert_eq!((header.index, header.cardinality), (2, 4));
+    }
+
+    #[test]
+    fn observation_slots_have_no_implicit_padding() {
+        assert_eq!(std::mem::size_of::<Observation>(), 96);
+        assert_eq!(Observations::HEADER_LEN % 8, 0);
+        assert_eq!(Observations::slots(Observations::space(1_000) + 95), 1_000);
+    }
+
+    #[test]
+    fn interpolation_multiplies_before_dividing() {
+        let start = Observation { timestamp: 0, initialized: 1, ..Default::default() };
+        let end = Observation {
+            liquidity_cumulative: 3,
+            timestamp: 4,
+            price_a_cumulative: cumulative(3),
+            price_b_cumulative: cumulative(u128::MAX),
+            initialized: 1,
+            padding: [0; 7]
+        };
+        let observation = start.interpolate(&end, 2);
+        assert_eq!(observation.price_a_cumulative, cumulative(1));
+        assert_eq!(observation.price_b_cumulative, cumulative(u128::MAX / 2));
+        assert_eq!(observation.liquidity_cumulative, 1);
+    }
+
+    #[test]
+    fn each_curve_type_has_its_own_seed() {
+        let curves = [
+            PoolCurve::ConstantProduct,
+            PoolCurve::Stable { amp: 100 },
+            PoolCurve::Weighted { weight_a: 500_000 },
+            PoolCurve::ConstantSum { sum_rate: 1 }
+        ];
+        for (i, a) in curves.iter().enumerate() {
+            for b in &curves[i + 1..] {
+                assert_ne!(a.curve_type().seed(), b.curve_type().seed());
+            }
+        }
+    }
 }
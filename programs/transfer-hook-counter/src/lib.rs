@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, InitializeExtraAccountMetaListInstruction};
+
+declare_id!("vubHP2x86GVQ5eowCS5Gcp7yYDGV9DEpkPTrBwHihoS");
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &str = "extra-account-metas";
+pub const COUNTER_SEED: &str = "counter";
+
+// 测试用的 transfer hook：每次转账都要求传入按 mint 派生的计数账户并累加转账次数与数量
+#[program]
+pub mod transfer_hook_counter {
+    use super::*;
+
+    #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?
+        )?;
+
+        Ok(())
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.transfers = counter.transfers.checked_add(1).unwrap();
+        counter.amount = counter.amount.checked_add(amount).unwrap();
+
+        Ok(())
+    }
+}
+
+
+// execute 指令在 [source, mint, destination, owner, extra_account_meta_list] 之后追加的账户
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: COUNTER_SEED.as_bytes().to_vec() },
+                // 下标 1 为 mint
+                Seed::AccountKey { index: 1 }
+            ],
+            false,
+            true
+        )?
+    ])
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct Counter {
+    pub transfers: u64,
+    pub amount: u64,
+}
+
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: TLV data is written by the handler
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [
+            EXTRA_ACCOUNT_METAS_SEED.as_bytes(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Counter::INIT_SPACE,
+        seeds = [
+            COUNTER_SEED.as_bytes(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub counter: Account<'info, Counter>,
+
+    pub system_program: Program<'info, System>
+}
+
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, may be a PDA
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Validation account for this mint
+    #[account(
+        seeds = [
+            EXTRA_ACCOUNT_METAS_SEED.as_bytes(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            COUNTER_SEED.as_bytes(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub counter: Account<'info, Counter>,
+}
@@ -0,0 +1,109 @@
+import { web3 } from "@coral-xyz/anchor";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  addExtraAccountMetasForExecute,
+  createTransferCheckedInstruction,
+  getMint,
+  getTransferHook,
+} from "@solana/spl-token";
+
+// 剩余账户的顺序：
+// - deposit_multi_liquidity / withdraw_multi_liquidity 先按池内代币顺序放每个代币的
+//   [mint, 用户账户, 金库, 代币程序]，见 multiPoolRemainingAccounts
+// - 之后（其余指令则直接）放 resolveTransferHookAccounts 解析出的 hook 账户；
+//   程序按地址查找这些账户，彼此顺序不限，多笔转账共用的账户只需传一次
+
+// 指令内由程序发起的一笔转账
+export interface TransferLeg {
+  mint: web3.PublicKey;
+  source: web3.PublicKey;
+  destination: web3.PublicKey;
+  // source 的所有者，池子转出时为池子的 authority PDA
+  authority: web3.PublicKey;
+  // hook 的额外账户依赖转账数量时必须与链上实际转出的数量一致
+  amount: number | bigint;
+}
+
+// 多币种池中一个代币的账户
+export interface MultiPoolMember {
+  mint: web3.PublicKey;
+  userAccount: web3.PublicKey;
+  vault: web3.PublicKey;
+  tokenProgram: web3.PublicKey;
+}
+
+// 按每笔转账 mint 的 ExtraAccountMetaList 解析 transfer hook 需要的账户，
+// 返回 hook 程序、校验账户与解析出的账户，去重后可直接作为剩余账户传入
+export async function resolveTransferHookAccounts(
+  connection: web3.Connection,
+  legs: TransferLeg[],
+  commitment?: web3.Commitment
+): Promise<web3.AccountMeta[]> {
+  const accounts = new Map<string, web3.AccountMeta>();
+
+  for (const leg of legs) {
+    const mintAccount = await connection.getAccountInfo(leg.mint, commitment);
+    if (!mintAccount || !mintAccount.owner.equals(TOKEN_2022_PROGRAM_ID)) {
+      continue;
+    }
+
+    const mint = await getMint(connection, leg.mint, commitment, TOKEN_2022_PROGRAM_ID);
+    const transferHook = getTransferHook(mint);
+    if (!transferHook || transferHook.programId.equals(web3.PublicKey.default)) {
+      continue;
+    }
+
+    const ix = createTransferCheckedInstruction(
+      leg.source,
+      leg.mint,
+      leg.destination,
+      leg.authority,
+      leg.amount,
+      mint.decimals,
+      [],
+      TOKEN_2022_PROGRAM_ID
+    );
+    await addExtraAccountMetasForExecute(
+      connection,
+      ix,
+      transferHook.programId,
+      leg.source,
+      leg.mint,
+      leg.destination,
+      leg.authority,
+      leg.amount,
+      commitment
+    );
+
+    // 前 4 个为转账本身的 [source, mint, destination, authority]
+    for (const meta of ix.keys.slice(4)) {
+      const key = meta.pubkey.toBase58();
+      const existing = accounts.get(key);
+      accounts.set(key, {
+        pubkey: meta.pubkey,
+        isSigner: false,
+        isWritable: meta.isWritable || (existing?.isWritable ?? false),
+      });
+    }
+  }
+
+  return [...accounts.values()];
+}
+
+// deposit_multi_liquidity / withdraw_multi_liquidity 的剩余账户
+export function multiPoolRemainingAccounts(
+  members: MultiPoolMember[],
+  hookAccounts: web3.AccountMeta[]
+): web3.AccountMeta[] {
+  const accounts: web3.AccountMeta[] = [];
+  for (const member of members) {
+    accounts.push(
+      { pubkey: member.mint, isSigner: false, isWritable: false },
+      { pubkey: member.userAccount, isSigner: false, isWritable: true },
+      { pubkey: member.vault, isSigner: false, isWritable: true },
+      { pubkey: member.tokenProgram, isSigner: false, isWritable: false }
+    );
+  }
+
+  return accounts.concat(hookAccounts);
+}
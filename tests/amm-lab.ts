@@ -1,16 +1,228 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program, web3 } from "@coral-xyz/anchor";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferHookInstruction,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
 import { AmmLab } from "../target/types/amm_lab";
+import { TransferHookCounter } from "../target/types/transfer_hook_counter";
+import { TransferLeg, resolveTransferHookAccounts } from "../sdk/transfer-hook";
 
 describe("amm-lab", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.ammLab as Program<AmmLab>;
+  const hookProgram = anchor.workspace.transferHookCounter as Program<TransferHookCounter>;
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  const decimals = 6;
+  const ammId = web3.Keypair.generate().publicKey;
+  const hookMint = web3.Keypair.generate();
+  let plainMint: web3.PublicKey;
+
+  const [amm] = web3.PublicKey.findProgramAddressSync([ammId.toBuffer()], program.programId);
+  // 两币池的地址带有曲线类型的种子，恒定乘积为 0；该种子无法从 IDL 推导，需要显式传入这些账户
+  const constantProductSeed = Buffer.from([0]);
+  const poolPda = (...suffix: Buffer[]) =>
+    web3.PublicKey.findProgramAddressSync(
+      [amm.toBuffer(), hookMint.publicKey.toBuffer(), plainMint.toBuffer(), constantProductSeed, ...suffix],
+      program.programId
+    )[0];
+  const poolAccounts = () => ({
+    pool: poolPda(),
+    poolAuthority: poolPda(Buffer.from("authority")),
+    mintLiquidity: poolPda(Buffer.from("liquidity")),
+  });
+  const [counter] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), hookMint.publicKey.toBuffer()],
+    hookProgram.programId
+  );
+  const ata = (mint: web3.PublicKey, owner: web3.PublicKey) =>
+    getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+
+  before(async () => {
+    // a 为带 transfer hook 的 Token-2022 代币，b 为普通 Token-2022 代币
+    const space = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(space);
+    await web3.sendAndConfirmTransaction(
+      connection,
+      new web3.Transaction().add(
+        web3.SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: hookMint.publicKey,
+          space,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferHookInstruction(
+          hookMint.publicKey,
+          payer.publicKey,
+          hookProgram.programId,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(hookMint.publicKey, decimals, payer.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [payer, hookMint]
+    );
+    await hookProgram.methods
+      .initializeExtraAccountMetaList()
+      .accountsPartial({ payer: payer.publicKey, mint: hookMint.publicKey })
+      .rpc();
+
+    plainMint = await createMint(
+      connection,
+      payer,
+      payer.publicKey,
+      null,
+      decimals,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    for (const mint of [hookMint.publicKey, plainMint]) {
+      const account = await createAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        payer.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(connection, payer, mint, account, payer, 1_000_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+    }
+
+    await program.methods
+      .createAmm(ammId, 30, decimals, new BN(1_000))
+      .accountsPartial({ signer: payer.publicKey, admin: payer.publicKey })
+      .rpc();
+  });
+
+  it("creates a pool and swaps through a transfer hook mint", async () => {
+    const authority = poolAccounts().poolAuthority;
+    const depositLegs: TransferLeg[] = [
+      {
+        mint: hookMint.publicKey,
+        source: ata(hookMint.publicKey, payer.publicKey),
+        destination: ata(hookMint.publicKey, authority),
+        authority: payer.publicKey,
+        amount: 100_000_000,
+      },
+      {
+        mint: plainMint,
+        source: ata(plainMint, payer.publicKey),
+        destination: ata(plainMint, authority),
+        authority: payer.publicKey,
+        amount: 100_000_000,
+      },
+    ];
+
+    await program.methods
+      .createPoolWithLiquidity({ constantProduct: {} }, new BN(100_000_000), new BN(100_000_000))
+      .accountsPartial({
+        create: {
+          signer: payer.publicKey,
+          aMint: hookMint.publicKey,
+          bMint: plainMint,
+          amm,
+          ...poolAccounts(),
+        },
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(await resolveTransferHookAccounts(connection, depositLegs))
+      .rpc();
+
+    let state = await hookProgram.account.counter.fetch(counter);
+    expect(state.transfers.toNumber()).to.equal(1);
+    expect(state.amount.toNumber()).to.equal(100_000_000);
+
+    // 用 b 换 a：池子以 authority PDA 转出带 hook 的代币
+    const input = 1_000_000;
+    const before = await getAccount(
+      connection,
+      ata(hookMint.publicKey, payer.publicKey),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const swapLegs: TransferLeg[] = [
+      {
+        mint: plainMint,
+        source: ata(plainMint, payer.publicKey),
+        destination: ata(plainMint, authority),
+        authority: payer.publicKey,
+        amount: input,
+      },
+      {
+        mint: hookMint.publicKey,
+        source: ata(hookMint.publicKey, authority),
+        destination: ata(hookMint.publicKey, payer.publicKey),
+        authority,
+        // 计数账户只由 mint 派生，不依赖转出数量
+        amount: 0,
+      },
+    ];
+
+    await program.methods
+      .swapExactTokensForTokens(false, new BN(input), new BN(1))
+      .accountsPartial({
+        payer: payer.publicKey,
+        trader: payer.publicKey,
+        aMint: hookMint.publicKey,
+        bMint: plainMint,
+        amm,
+        ...poolAccounts(),
+        observations: null,
+        priceFeed: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(await resolveTransferHookAccounts(connection, swapLegs))
+      .rpc();
+
+    const after = await getAccount(
+      connection,
+      ata(hookMint.publicKey, payer.publicKey),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const output = Number(after.amount - before.amount);
+    expect(output).to.be.greaterThan(0);
+
+    state = await hookProgram.account.counter.fetch(counter);
+    expect(state.transfers.toNumber()).to.equal(2);
+    expect(state.amount.toNumber()).to.equal(100_000_000 + output);
+  });
+
+  it("rejects a swap without the hook accounts", async () => {
+    let failed = false;
+    try {
+      await program.methods
+        .swapExactTokensForTokens(true, new BN(1_000_000), new BN(1))
+        .accountsPartial({
+          payer: payer.publicKey,
+          trader: payer.publicKey,
+          aMint: hookMint.publicKey,
+          bMint: plainMint,
+          amm,
+          ...poolAccounts(),
+          observations: null,
+          priceFeed: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    } catch {
+      failed = true;
+    }
+    expect(failed).to.equal(true);
   });
 });